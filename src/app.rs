@@ -1,5 +1,6 @@
 
 use std::{collections::HashMap, hash::Hash, io::SeekFrom};
+use std::collections::BTreeMap;
 use clap::builder::Str;
 use eframe::Frame;
 use egui::epaint::HAS_RAYON;
@@ -7,7 +8,6 @@ use egui::{Color32, DragValue};
 use egui::{FontData, FontDefinitions, FontFamily, RichText, Window, SidePanel};
 use guesses::{answer_grid, guesses_grid};
 use metrics::{MY_GREEN, MY_RED};
-use web_sys::console;   
 
 mod guesses;
 mod metrics;
@@ -15,6 +15,8 @@ mod letter;
 mod utils;
 mod keyboard;
 mod gamemaster;
+mod chart;
+mod toast;
 
 use crate::builtin_words;
 use crate::game::Game;
@@ -22,10 +24,39 @@ use crate::words_gen;
 use crate::GameState;
 use words_gen::*;
 use crate::game;
+use crate::solver;
+use crate::stats;
+use crate::multi::{self, MultiGame};
+use crate::daily;
+use crate::share;
 use game::LetterState;
 use crate::state::{self, GameMaster, GameMaster_new};
 use crate::config;
 
+//seconds between two guesses of a replay
+const REPLAY_STEP: f64 = 0.6;
+
+//A game of the history played again on the board, a guess at a time
+struct Replay {
+    game: MultiGame,
+    guesses: Vec<String>,
+    next: usize,
+    last: f64,
+}
+
+//What is kept for a player while another one is playing, the one playing has it in GuiApp itself
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+struct Profile {
+    win: i32,
+    lose: i32,
+    round: i32,
+    streak: Option<state::Streak>,
+    word_used: HashMap<String, i32>,
+    word_used_vec: Vec<(String, i32)>,
+    game_history: Vec<state::Game>,
+}
+
 #[derive(serde::Deserialize, serde::Serialize)]
 #[serde(default)] // if we add new fields, give them default values when deserializing old state
 pub struct GuiApp {
@@ -34,13 +65,44 @@ pub struct GuiApp {
     win: i32,
     lose: i32,
     round: i32,
+    //None in a storage from before the streak was kept
+    streak: Option<state::Streak>,
     day: u64,
     seed: u64,
+    max_guesses: i32,
     difficult: bool,
     word_used: HashMap<String, i32>,
     word_used_vec: Vec<(String, i32)>,
-    game_history: Vec<gamemaster::Game>,
+    //the same records as the state file, older histories are read by gamemaster::history
+    #[serde(deserialize_with = "gamemaster::history")]
+    game_history: Vec<state::Game>,
     config: config::GameConfig,
+    show_candidates: bool,
+    adversarial: bool,
+    daily_mode: bool,
+    high_contrast: bool,
+    //tiles flip and rejected rows shake
+    animations: bool,
+    //the date of the daily puzzle being played
+    daily: Option<String>,
+    //the seed and day the answers on the board were picked by, None for an answer given or kept open
+    picked: Option<(u64, u64)>,
+    boards: usize,
+    //the game on several boards, the single game above is not played while it is on
+    multi: Option<MultiGame>,
+    #[serde(skip)]
+    replay: Option<Replay>,
+    player: String,
+    //the other players, by name
+    profiles: BTreeMap<String, Profile>,
+    #[serde(skip)]
+    new_player: String,
+    //why the last guesses were rejected
+    #[serde(skip)]
+    toasts: toast::Toasts,
+    //FINAL words consistent with every guess so far, refreshed after each guess
+    #[serde(skip)]
+    candidates: Vec<String>,
 }
 
 impl Default for GuiApp {
@@ -51,13 +113,30 @@ impl Default for GuiApp {
             win: 0,
             lose: 0,
             round: 0,
+            streak: None,
             day: 1,
             seed: 2024,
+            max_guesses: game::MAX_GUESS_TIMES,
             difficult: false,
             word_used: HashMap::new(),
             word_used_vec: Vec::new(),
             game_history: Vec::new(),
-            config: config::GameConfig::new()
+            config: config::GameConfig::new(),
+            show_candidates: false,
+            adversarial: false,
+            daily_mode: false,
+            high_contrast: false,
+            animations: true,
+            daily: None,
+            picked: None,
+            boards: 1,
+            multi: None,
+            replay: None,
+            player: state::DEFAULT_PLAYER.to_string(),
+            profiles: BTreeMap::new(),
+            new_player: String::new(),
+            toasts: toast::Toasts::default(),
+            candidates: Vec::new(),
         }
     }
 }
@@ -90,22 +169,211 @@ impl GuiApp {
         if let None = app.game {
             app.renew_game(config::GameConfig::new_with_day(1));
         }
+        //a storage from before the streak was kept has it worked out from the history
+        if app.streak.is_none() {
+            app.streak = Some(state::Streak::from_games(&app.game_history));
+        }
+        app.update_candidates();
         app
     }
 
-    fn renew_game(&mut self, config: config::GameConfig){
-        let mut game = Game::new(config.random.unwrap_or(true),
-        config.difficult.unwrap_or(false),
-        config.day,
-        config.seed, 
-        false, 
-        config.final_set, 
-        config.acceptable_set);
+    fn renew_game(&mut self, mut config: config::GameConfig){
+        //the daily puzzle is the random answer of the day from the epoch, with a fixed seed.
+        //Once it is in the history the game is left as it is until tomorrow.
+        self.daily = None;
+        if config.daily.unwrap_or(false) {
+            let date = daily::today().to_string();
+            if self.played_daily(&date) {
+                return;
+            }
+            let epoch = config.epoch.as_ref().and_then(|e| e.parse().ok()).unwrap_or_else(daily::default_epoch);
+            //a multi-board puzzle takes a run of answers a day
+            let answers = load_words(&config).FINAL.len() / config.boards.unwrap_or(1).max(1);
+            config.random = Some(true);
+            config.seed = Some(daily::SEED);
+            config.day = Some(daily::day_of(daily::today(), epoch, answers));
+            self.daily = Some(date);
+        }
+
+        let mut word_bank = load_words(&config);
+
+        let boards = config.boards.unwrap_or(1);
+        if boards > 1 {
+            //a day past the answers leaves the game as it is
+            let answers = match word_bank.pick_answers(config.seed.unwrap_or(2024), config.day.unwrap_or(1), boards) {
+                Ok(answers) => answers,
+                Err(_) => return,
+            };
+            self.picked = Some((config.seed.unwrap_or(2024), config.day.unwrap_or(1)));
+            let max_guesses = config.max_guesses.unwrap_or(multi::default_max_guesses(boards));
+            self.multi = MultiGame::new(&answers, word_bank, config.difficult.unwrap_or(false), max_guesses).ok();
+            self.update_candidates();
+            return;
+        }
+        self.multi = None;
 
-        game.receive_answer(&config.word);
-        
-        self.game = Some(game);
+        if config.adversarial.unwrap_or(false) {
+            self.game = Game::adversarial(word_bank, config.difficult.unwrap_or(false), config.max_guesses.unwrap_or(game::MAX_GUESS_TIMES)).ok();
+            self.picked = None;
+            self.update_candidates();
+            return;
+        }
+
+        let (answer, picked) = match config.word {
+            Some(w) if !config.random.unwrap_or(true) => (w, None),
+            _ => match word_bank.pick_answer(config.seed.unwrap_or(2024), config.day.unwrap_or(1)) {
+                Ok(answer) => (answer, Some((config.seed.unwrap_or(2024), config.day.unwrap_or(1)))),
+                //a day past the answers leaves the game as it is
+                Err(_) => return,
+            }
+        };
+
+        if let Ok(game) = Game::new(&answer, word_bank, config.difficult.unwrap_or(false), config.max_guesses.unwrap_or(game::MAX_GUESS_TIMES)) {
+            self.game = Some(game);
+            self.picked = picked;
+        }
+        self.update_candidates();
+    }
+
+    fn update_candidates(&mut self) {
+        //on several boards the candidates are those of the first board not solved yet
+        if let Some(multi) = &self.multi {
+            let board = multi.boards.iter().find(|b| b.game_state == GameState::Going).unwrap_or(&multi.boards[0]);
+            self.candidates = solver::candidates(board);
+            self.candidates.sort();
+        }
+        else if let Some(game) = &self.game {
+            self.candidates = solver::candidates(game);
+            self.candidates.sort();
+        }
+    }
+
+    //Put the records of the player away and take out those of another one
+    fn switch_player(&mut self, name: String) {
+        if name == self.player {
+            return;
+        }
+        let next = self.profiles.remove(&name).unwrap_or_default();
+        let current = Profile {
+            win: std::mem::replace(&mut self.win, next.win),
+            lose: std::mem::replace(&mut self.lose, next.lose),
+            round: std::mem::replace(&mut self.round, next.round),
+            streak: std::mem::replace(&mut self.streak, next.streak),
+            word_used: std::mem::replace(&mut self.word_used, next.word_used),
+            word_used_vec: std::mem::replace(&mut self.word_used_vec, next.word_used_vec),
+            game_history: std::mem::replace(&mut self.game_history, next.game_history),
+        };
+        self.profiles.insert(std::mem::replace(&mut self.player, name), current);
+        self.replay = None;
     }
+
+    //Put a game of the history on the board, its guesses are played by update() one after another
+    //with the word-sets the game was played with, those of the settings for an older record
+    fn start_replay(&mut self, ctx: &egui::Context, index: usize) {
+        let record = &self.game_history[index];
+        let answers = if record.answers.is_empty() { vec![record.answer.clone()] } else { record.answers.clone() };
+        let max_guesses = record.allowed_guesses();
+
+        let words = match &record.words {
+            Some(sets) => sets.to_words(),
+            None => Ok(load_words(&self.config)),
+        };
+        let game = match words {
+            Ok(words) => MultiGame::new(&answers, words, false, max_guesses).map_err(|_| "Answer not in word list".to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        match game {
+            Ok(game) => self.replay = Some(Replay {
+                game,
+                guesses: record.guesses.clone(),
+                next: 0,
+                last: 0.0,
+            }),
+            Err(e) => self.toasts.add(ctx, format!("Cannot replay: {}", e)),
+        }
+    }
+
+    fn played_daily(&self, date: &str) -> bool {
+        self.game_history.iter().any(|h| h.daily.as_deref() == Some(date))
+    }
+
+    fn game_state(&self) -> GameState {
+        match &self.multi {
+            Some(multi) => multi.game_state,
+            None => self.game.as_ref().unwrap().game_state,
+        }
+    }
+
+    fn length(&self) -> usize {
+        match &self.multi {
+            Some(multi) => multi.length(),
+            None => self.game.as_ref().unwrap().length(),
+        }
+    }
+
+    fn max_guesses(&self) -> i32 {
+        match &self.multi {
+            Some(multi) => multi.max_guesses,
+            None => self.game.as_ref().unwrap().max_guesses,
+        }
+    }
+
+    //Submit the guess typed to the game being played
+    fn submit_guess(&mut self) -> Result<GameState, game::Error> {
+        match &mut self.multi {
+            Some(multi) => multi.guess(&self.guess).map(|_| multi.game_state),
+            None => self.game.as_mut().unwrap().guess(&self.guess).map(|r| r.game_state),
+        }
+    }
+
+    //Record a finished game into the history and the statistics
+    fn finish_game(&mut self) {
+        let (record, win, round) = match &self.multi {
+            Some(multi) => (multi.to_state(), multi.game_state == GameState::Win, multi.round - 1),
+            None => {
+                let game = self.game.as_ref().unwrap();
+                (state::Game { 
+                    answer: game.answer.to_uppercase(), 
+                    guesses: game.guesses.clone(),
+                    hints: game.hints,
+                    max_guesses: Some(game.max_guesses),
+                    ..state::Game_new()
+                }, game.game_state == GameState::Win, game.round - 1)
+            }
+        };
+
+        //seed and day only tell the answer of a random game, the daily one included, as the CLI keeps them
+        self.game_history.push(state::Game { 
+            daily: self.daily.clone(),
+            date: Some(chrono::Utc::now()),
+            seed: self.picked.map(|(seed, _)| seed),
+            day: self.picked.map(|(_, day)| day),
+            difficult: Some(self.difficult),
+            words: Some(state::WordSets {
+                final_set: self.config.final_set.clone(),
+                acceptable_set: self.config.acceptable_set.clone(),
+                length: self.length(),
+                external: self.config.allow_external_words.unwrap_or(false),
+            }),
+            win: Some(win),
+            ..record });
+
+        self.streak.get_or_insert_with(state::Streak::default).record(win);
+        if win {
+            self.win += 1;
+            self.round += round;
+        }
+        else {
+            self.lose += 1;
+        }
+    }
+}
+
+//The word bank the config asks for, the builtin one if its files cannot be used
+fn load_words(config: &config::GameConfig) -> Words {
+    Words::from_files(config.length.unwrap_or(BUILTIN_LENGTH), config.final_set.clone(), config.acceptable_set.clone(),
+        config.allow_external_words.unwrap_or(false))
+        .unwrap_or_else(|_| Words::new())
 }
 
 impl eframe::App for GuiApp {
@@ -152,10 +420,28 @@ impl eframe::App for GuiApp {
             ui.vertical(|ui| {
 
             ui.label(RichText::new("Wordle").family(FontFamily::Name("NY".into())).size(24.0).color(Color32::GOLD));
-            ui.label(RichText::new("- Guess a word within 6 tries!").family(FontFamily::Name("SFM".into())).size(14.0).italics());
+            ui.label(RichText::new(format!("- Guess a word within {} tries!", self.max_guesses())).family(FontFamily::Name("SFM".into())).size(14.0).italics());
             ui.label(RichText::new("- Press enter for a new game").family(FontFamily::Name("SFM".into())).size(14.0).italics().color(Color32::PLACEHOLDER));
             ui.label(RichText::new("  every time you win or lose.").family(FontFamily::Name("SFM".into())).size(14.0).italics().color(Color32::PLACEHOLDER));
 
+            //the result grid of a finished game goes to the clipboard
+            if self.game_state() != GameState::Going
+                && ui.button(RichText::new("Share").family(FontFamily::Name("SFM".into())).size(14.0)).clicked() {
+                let text = match &self.multi {
+                    Some(multi) => share::share_multi(multi, self.picked.map(|(_, day)| day), self.high_contrast),
+                    None => share::share_text(self.game.as_ref().unwrap(), self.picked.map(|(_, day)| day), self.high_contrast),
+                };
+                ui.output_mut(|o| o.copied_text = text);
+            }
+
+            //the countdown ticks every second
+            if self.config.daily.unwrap_or(false) {
+                let left = daily::until_next().num_seconds();
+                let played = if self.played_daily(&daily::today().to_string()) { "Played! " } else { "" };
+                ui.label(RichText::new(format!("- {}Next daily in {:02}:{:02}:{:02}", played, left / 3600, left / 60 % 60, left % 60)).family(FontFamily::Name("SFM".into())).size(14.0).italics().color(Color32::GOLD));
+                ctx.request_repaint_after(std::time::Duration::from_secs(1));
+            }
+
 
             egui::CollapsingHeader::new(RichText::new("Settings").family(FontFamily::Name("SFM".into())).size(20.0)).show(ui, |ui| {
                 
@@ -163,15 +449,25 @@ impl eframe::App for GuiApp {
 
                 if self.difficult {
                     self.config.difficult = Some(true);
-                    self.game.as_mut().unwrap().difficult = true;
                 }
                 else {
                     self.config.difficult = None;
-                    self.game.as_mut().unwrap().difficult = false;
+                }
+                self.game.as_mut().unwrap().difficult = self.difficult;
+                if let Some(multi) = &mut self.multi {
+                    for board in &mut multi.boards {
+                        board.difficult = self.difficult;
+                    }
                 }
 
                 
 
+                //taken at the next Enter, as the other settings below
+                ui.checkbox(&mut self.adversarial, RichText::new("Absurdle").family(FontFamily::Name("SFM".into())).size(14.0));
+                ui.checkbox(&mut self.daily_mode, RichText::new("Daily").family(FontFamily::Name("SFM".into())).size(14.0));
+                ui.checkbox(&mut self.high_contrast, RichText::new("High contrast share").family(FontFamily::Name("SFM".into())).size(14.0));
+                ui.checkbox(&mut self.animations, RichText::new("Animations").family(FontFamily::Name("SFM".into())).size(14.0));
+
                 ui.label(RichText::new("Seed").family(FontFamily::Name("SFM".into())).size(14.0));
                 
                 ui.add(DragValue::new(&mut self.seed));
@@ -181,19 +477,86 @@ impl eframe::App for GuiApp {
                 
                 ui.add(DragValue::new(&mut self.day));
 
+                
+                ui.label(RichText::new("Max guesses").family(FontFamily::Name("SFM".into())).size(14.0));
+                
+                ui.add(DragValue::new(&mut self.max_guesses).range(1..=15));
+
+                
+                ui.label(RichText::new("Boards").family(FontFamily::Name("SFM".into())).size(14.0));
+                
+                //picking the boards brings the max guesses back to its default for them
+                ui.horizontal(|ui| {
+                    for n in [1, 2, 4, 8] {
+                        if ui.radio_value(&mut self.boards, n, n.to_string()).clicked() {
+                            self.max_guesses = if n > 1 { multi::default_max_guesses(n) } else { game::MAX_GUESS_TIMES };
+                        }
+                    }
+                });
+
                 ui.label("Config");
                 if ui.button("Enter").clicked() {
                     self.config.seed = Some(self.seed);
                     self.config.day = Some(self.day);
+                    self.config.max_guesses = Some(self.max_guesses);
+                    self.config.boards = if self.boards > 1 { Some(self.boards) } else { None };
+                    self.config.adversarial = if self.adversarial { Some(true) } else { None };
+                    self.config.daily = if self.daily_mode { Some(true) } else { None };
                     self.guess.clear();
                     self.renew_game(self.config.clone());
                 }
             });
+            egui::CollapsingHeader::new(RichText::new("Players").family(FontFamily::Name("SFM".into())).size(20.0)).show(ui, |ui| {
+
+                let mut selected = self.player.clone();
+                egui::ComboBox::from_label(RichText::new("Player").family(FontFamily::Name("SFM".into())).size(14.0))
+                    .selected_text(RichText::new(&selected).family(FontFamily::Name("SFM".into())).size(14.0))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut selected, self.player.clone(), RichText::new(&self.player).family(FontFamily::Name("SFM".into())).size(14.0));
+                        for name in self.profiles.keys() {
+                            ui.selectable_value(&mut selected, name.clone(), RichText::new(name).family(FontFamily::Name("SFM".into())).size(14.0));
+                        }
+                    });
+                ui.horizontal(|ui| {
+                    ui.text_edit_singleline(&mut self.new_player);
+                    let name = self.new_player.trim().to_string();
+                    if ui.button("Add").clicked() && !name.is_empty() {
+                        selected = name;
+                        self.new_player.clear();
+                    }
+                });
+                self.switch_player(selected);
+
+                //the same ranking as the leaderboard command
+                ui.label(RichText::new("Leaderboard:").family(FontFamily::Name("SFM".into())).size(14.0));
+                let mut players: Vec<(&str, &[state::Game])> = vec![(self.player.as_str(), self.game_history.as_slice())];
+                for (name, p) in &self.profiles {
+                    players.push((name.as_str(), p.game_history.as_slice()));
+                }
+                egui::Grid::new("leaderboard").show(ui, |ui| {
+                    for head in ["#", "Player", "Win%", "Avg", "Streak"] {
+                        ui.label(RichText::new(head).family(FontFamily::Name("SFM".into())).size(12.0));
+                    }
+                    ui.end_row();
+                    for (i, s) in stats::leaderboard(&players).iter().enumerate() {
+                        ui.label(RichText::new(format!("{}", i + 1)).family(FontFamily::Name("SFM".into())).size(12.0));
+                        ui.label(RichText::new(&s.player).family(FontFamily::Name("SFM".into())).size(12.0));
+                        ui.label(RichText::new(format!("{:.0}", s.win_percentage)).family(FontFamily::Name("SFM".into())).size(12.0));
+                        ui.label(RichText::new(format!("{:.2}", s.average)).family(FontFamily::Name("SFM".into())).size(12.0));
+                        ui.label(RichText::new(format!("{}", s.current_streak)).family(FontFamily::Name("SFM".into())).size(12.0));
+                        ui.end_row();
+                    }
+                });
+            });
+
             egui::CollapsingHeader::new(RichText::new("Statistics").family(FontFamily::Name("SFM".into())).size(20.0)).show(ui, |ui| {
                 
                 ui.label(RichText::new(format!("Win: {}", self.win)).family(FontFamily::Name("SFM".into())).size(14.0).color(metrics::MY_GREEN));
                 ui.label(RichText::new(format!("Fail: {}", self.lose)).family(FontFamily::Name("SFM".into())).size(14.0).color(metrics::MY_RED));
                 ui.label(RichText::new(format!("Average Rounds: {}", self.round as f64 / self.win as f64)).family(FontFamily::Name("SFM".into())).size(14.0));
+                let streak = self.streak.unwrap_or_default();
+                ui.label(RichText::new(format!("Current Streak: {}", streak.current)).family(FontFamily::Name("SFM".into())).size(14.0).color(metrics::MY_GREEN));
+                ui.label(RichText::new(format!("Best Streak: {}", streak.best)).family(FontFamily::Name("SFM".into())).size(14.0));
 
                 ui.label("\n");
                 ui.label(RichText::new("Most used words:").family(FontFamily::Name("SFM".into())).size(14.0));
@@ -207,37 +570,87 @@ impl eframe::App for GuiApp {
                     cnt += 1;
                 }
 
+                //the full report is worked out from the history, the same as the stats command does from the state file
+                let report = stats::compute(&self.game_history);
+                ui.label("\n");
+                ui.label(RichText::new(format!("Played: {}  Win%: {:.0}", report.games, report.win_percentage)).family(FontFamily::Name("SFM".into())).size(14.0));
+                ui.label(RichText::new("Guess distribution:").family(FontFamily::Name("SFM".into())).size(14.0));
+                let last = self.game_history.last().filter(|g| g.is_win()).map(|g| g.guesses.len() - 1);
+                chart::histogram(ui, &report.histogram, last);
+
+                ui.label(RichText::new("Best starting words:").family(FontFamily::Name("SFM".into())).size(14.0));
+                for s in report.starts.iter().take(5) {
+                    ui.label(RichText::new(format!("{}: {:.2}", s.word, s.average)).family(FontFamily::Name("SFM".into())).size(14.0));
+                }
+                ui.label(RichText::new("Hardest answers:").family(FontFamily::Name("SFM".into())).size(14.0));
+                for a in report.answers.iter().take(5) {
+                    ui.label(RichText::new(format!("{}: {:.2}", a.word, a.average)).family(FontFamily::Name("SFM".into())).size(14.0));
+                }
+                ui.label(RichText::new("Letters guessed:").family(FontFamily::Name("SFM".into())).size(14.0));
+                ui.horizontal_wrapped(|ui| {
+                    for l in &report.letters {
+                        ui.label(RichText::new(format!("{}:{}", l.letter, l.count)).family(FontFamily::Name("SFM".into())).size(12.0));
+                    }
+                });
+
                 if ui.button("Reset").clicked() {
                     self.win = 0;
                     self.lose = 0;
                     self.round = 0;
+                    self.streak = Some(state::Streak::default());
                     self.word_used = HashMap::new();
                     self.word_used_vec = Vec::new();
                     
                 }
         });
 
+            egui::CollapsingHeader::new(RichText::new("Candidates").family(FontFamily::Name("SFM".into())).size(20.0)).show(ui, |ui| {
+                
+                ui.label(RichText::new(format!("{} answers left", self.candidates.len())).family(FontFamily::Name("SFM".into())).size(14.0));
+                ui.checkbox(&mut self.show_candidates, RichText::new("Show the list").family(FontFamily::Name("SFM".into())).size(14.0));
+
+                if self.show_candidates {
+                    ui.horizontal_wrapped(|ui| {
+                        for i in &self.candidates {
+                            ui.label(RichText::new(i.to_uppercase()).family(FontFamily::Name("SFM".into())).size(14.0).color(Color32::PLACEHOLDER));
+                        }
+                    });
+                }
+        });
+
             egui::CollapsingHeader::new(RichText::new("GameHistory").family(FontFamily::Name("SFM".into())).size(20.0)).show(ui, |ui| {
                 
-                for i in &self.game_history {
-                    egui::CollapsingHeader::new(RichText::new(format!("{}", i.date)).family(FontFamily::Name("SFM".into())).size(14.0)).show(ui, |ui| {
+                let mut replay = None;
+                for (index, i) in self.game_history.iter().enumerate() {
+                    let date = i.date.map(|d| d.to_string()).unwrap_or_default();
+                    egui::CollapsingHeader::new(RichText::new(date).family(FontFamily::Name("SFM".into())).size(14.0)).id_source(index).show(ui, |ui| {
                         
-                        ui.label(RichText::new(format!("seed: {}, day: {}", i.seed, i.day)).family(FontFamily::Name("SFM".into())).size(14.0));
+                        if let (Some(seed), Some(day)) = (i.seed, i.day) {
+                            ui.label(RichText::new(format!("seed: {}, day: {}", seed, day)).family(FontFamily::Name("SFM".into())).size(14.0));
+                        }
                         ui.label(RichText::new(format!("answer:")).family(FontFamily::Name("SFM".into())).size(14.0));
-                        ui.label(RichText::new(format!("{}", i.game.answer.to_uppercase())).family(FontFamily::Name("SFM".into())).size(14.0).color(MY_GREEN));
+                        let answers = if i.answers.is_empty() { i.answer.to_uppercase() } else { i.answers.join(" ") };
+                        let round = i.guesses.len() as i32;
+                        ui.label(RichText::new(answers).family(FontFamily::Name("SFM".into())).size(14.0).color(MY_GREEN));
                         ui.label(RichText::new(format!("guesses:")).family(FontFamily::Name("SFM".into())).size(14.0));
-                        for j in 0..i.round - 1 {
-                            ui.label(RichText::new(format!("{}", i.game.guesses[j as usize])).family(FontFamily::Name("SFM".into())).size(14.0).color(MY_RED));
+                        for j in 0..round - 1 {
+                            ui.label(RichText::new(format!("{}", i.guesses[j as usize])).family(FontFamily::Name("SFM".into())).size(14.0).color(MY_RED));
                         }
-                        if i.win {
-                            ui.label(RichText::new(format!("{}", i.game.guesses[(i.round - 1) as usize])).family(FontFamily::Name("SFM".into())).size(14.0).color(MY_GREEN));
+                        if i.is_win() {
+                            ui.label(RichText::new(format!("{}", i.guesses[(round - 1) as usize])).family(FontFamily::Name("SFM".into())).size(14.0).color(MY_GREEN));
                         }
                         else {
-                            ui.label(RichText::new(format!("{}", i.game.guesses[(i.round - 1) as usize])).family(FontFamily::Name("SFM".into())).size(14.0).color(MY_RED));
+                            ui.label(RichText::new(format!("{}", i.guesses[(round - 1) as usize])).family(FontFamily::Name("SFM".into())).size(14.0).color(MY_RED));
+                        }
+                        if ui.button("Replay").clicked() {
+                            replay = Some(index);
                         }
                 });
                     
                 }
+                if let Some(index) = replay {
+                    self.start_replay(ctx, index);
+                }
 
                 if ui.button("Clear").clicked() {
                     
@@ -249,91 +662,111 @@ impl eframe::App for GuiApp {
     });
 });
 
-            guesses_grid(ui, self.game.as_ref().unwrap() , &self.guess, self.difficult);
+            //a replay takes a step once in a while, and any key brings the game back
+            let now = ctx.input(|i| i.time);
+            if let Some(replay) = &mut self.replay {
+                if replay.next < replay.guesses.len() && now - replay.last > REPLAY_STEP {
+                    let _ = replay.game.guess(&replay.guesses[replay.next]);
+                    replay.next += 1;
+                    replay.last = now;
+                }
+                ctx.request_repaint_after(std::time::Duration::from_secs_f64(REPLAY_STEP));
+            }
+
+            //a rejected guess sets the shake to 1, from where it runs down to 0 and the row swings less and less
+            let shake_id = egui::Id::new("shake");
+            let shake = if self.animations {
+                let s = ctx.animate_value_with_time(shake_id, 0.0, metrics::SHAKE);
+                metrics::SHAKE_WIDTH * s * (s * 6.0 * std::f32::consts::PI).sin()
+            }
+            else {
+                0.0
+            };
+
+            let pressed = if let Some(replay) = &self.replay {
+                let boards = replay.game.boards.len() as i32;
+                for (i, board) in replay.game.boards.iter().enumerate() {
+                    guesses_grid(ui, board, &String::new(), self.difficult, (i as i32, boards), self.animations, 0.0);
+                }
+                let all: Vec<&Game> = replay.game.boards.iter().collect();
+                keyboard::keyboard(ui, &all, self.difficult)
+            }
+            else if let Some(multi) = &self.multi {
+                let boards = multi.boards.len() as i32;
+                let empty = String::new();
+                for (i, board) in multi.boards.iter().enumerate() {
+                    //only the boards still going show the guess being typed
+                    let guess = if board.game_state == GameState::Going { &self.guess } else { &empty };
+                    guesses_grid(ui, board, guess, self.difficult, (i as i32, boards), self.animations, shake);
+
+                    if multi.game_state == GameState::Lose && board.game_state != GameState::Win {
+                        answer_grid(ui, &board.answer, &board.answer_state, multi.max_guesses, self.difficult, (i as i32, boards));
+                    }
+                }
 
-            if let game::GameState::Lose = self.game.as_ref().unwrap().game_state {
-                answer_grid(ui, &self.game.as_ref().unwrap().answer, self.game.as_ref().unwrap().answer_state, self.difficult);
+                //the solved boards no longer colour the keys
+                let mut going: Vec<&Game> = multi.boards.iter().filter(|b| b.game_state == GameState::Going).collect();
+                if going.is_empty() {
+                    going = multi.boards.iter().collect();
+                }
+                keyboard::keyboard(ui, &going, self.difficult)
             }
+            else {
+                let game = self.game.as_ref().unwrap();
+                guesses_grid(ui, game, &self.guess, self.difficult, (0, 1), self.animations, shake);
 
-            if let Some(key) = keyboard::keyboard(ui, self.game.as_ref().unwrap(), self.difficult) {
+                if let game::GameState::Lose = game.game_state {
+                    answer_grid(ui, &game.answer, &game.answer_state, game.max_guesses, self.difficult, (0, 1));
+                }
+
+                keyboard::keyboard(ui, &[game], self.difficult)
+            };
+
+            let pressed = if self.replay.is_some() && pressed.is_some() {
+                self.replay = None;
+                None
+            }
+            else {
+                pressed
+            };
+
+            if let Some(key) = pressed {
                 match key {
                     '\n' => {
-                        if let game::GameState::FullString = self.game.as_ref().unwrap().game_state {
-                            match self.game.as_ref().unwrap().check(&self.guess.to_lowercase()) {
-                                Ok(()) => {
-                                    console::log_1(&"Arrive here".into());
-                                    self.game.as_mut().unwrap().round += 1;
-                                    self.game.as_mut().unwrap().letters_update(&self.guess);
-
-                                    self.game_history.push( gamemaster::Game { 
-                                        game: state::Game { 
-                                            answer: self.game.as_ref().unwrap().answer.clone(), 
-                                            guesses: self.game.as_ref().unwrap().guesses.clone()
-                                         }, 
-                                        seed: self.seed.clone(), 
-                                        day: self.day.clone(), 
-                                        date: chrono::Utc::now(),
-                                        win: true,
-                                        round: self.game.as_ref().unwrap().round - 1});
-
-                                    *self.word_used.entry(self.guess.clone()).or_insert(0) += 1;
-                                    
-                                    self.word_used_vec = self.word_used.clone().into_iter().collect();
-                                    
-                                    self.word_used_vec.sort_by(|a, b| a.0.cmp(&b.0));
-                                    self.word_used_vec.sort_by(|a, b| b.1.cmp(&a.1));
-
-                                    self.guess.clear();
-                                    self.game.as_mut().unwrap().game_state = GameState::Win;
-                                    self.win += 1;
-                                    self.round += 1;
-                                    console::log_1(&"Out here".into());
-                                    
-                                }
-                                Err(game::Error::WrongAnswer) => {
-                                    console::log_1(&"Arrive Wrongly here".into());
-                                    self.game.as_mut().unwrap().round += 1;
-                                    
-                                    self.game.as_mut().unwrap().letters_update(&self.guess);
-
-
-                                    *self.word_used.entry(self.guess.clone()).or_insert(0) += 1;
-
-                                    self.word_used_vec = self.word_used.clone().into_iter().collect();
-                                    
-                                    self.word_used_vec.sort_by(|a, b| a.0.cmp(&b.0));
-                                    self.word_used_vec.sort_by(|a, b| b.1.cmp(&a.1));
-                                    
-                                    self.game.as_mut().unwrap().game_state = GameState::Going;
-                                    
-                                    if self.game.as_ref().unwrap().round == 7 {
-                                        self.game.as_mut().unwrap().game_state = GameState::Lose;
-                                        self.lose += 1;
-                                        self.round += 1;
-
-                                        self.game_history.push( gamemaster::Game { 
-                                            game: state::Game { 
-                                                answer: self.game.as_ref().unwrap().answer.clone(), 
-                                                guesses: self.game.as_ref().unwrap().guesses.clone()
-                                             }, 
-                                            seed: self.seed.clone(), 
-                                            day: self.day.clone(), 
-                                            date: chrono::Utc::now(),
-                                            win: false,
-                                            round: self.game.as_ref().unwrap().round - 1 });
+                        if let GameState::Going = self.game_state() {
+                            if self.guess.len() < self.length() {
+                                self.toasts.add(ctx, "Not enough letters".to_string());
+                                ctx.animate_value_with_time(shake_id, 1.0, 0.0);
+                            }
+                            else {
+                                match self.submit_guess() {
+                                    Ok(game_state) => {
+                                        *self.word_used.entry(self.guess.clone()).or_insert(0) += 1;
+
+                                        self.word_used_vec = self.word_used.clone().into_iter().collect();
+                                        
+                                        self.word_used_vec.sort_by(|a, b| a.0.cmp(&b.0));
+                                        self.word_used_vec.sort_by(|a, b| b.1.cmp(&a.1));
+
+                                        self.guess.clear();
+                                        self.update_candidates();
+
+                                        if game_state != GameState::Going {
+                                            self.finish_game();
+                                        }
                                     }
-
-                                    self.guess.clear();
-
-                                }
-                                Err(game::Error::DisobeyingDifficult) => {
-                                    console::log_1(&"Hint not used".into());
-                                    console::log_1(&self.guess.clone().into());
+                                    Err(game::Error::DisobeyingDifficult(violation)) => {
+                                        self.toasts.add(ctx, violation.to_string());
+                                        ctx.animate_value_with_time(shake_id, 1.0, 0.0);
+                                    }
+                                    Err(game::Error::NotInWordList) => {
+                                        self.toasts.add(ctx, "Not in word list".to_string());
+                                        ctx.animate_value_with_time(shake_id, 1.0, 0.0);
+                                    }
+                                    _ => {}
                                 }
-                                _ => {}
                             }
                         }
-                        else if let game::GameState::Going = self.game.as_ref().unwrap().game_state {}
                         else {
                             let mut _day = self.config.day.clone().unwrap_or(1);
                             _day += 1;
@@ -344,28 +777,24 @@ impl eframe::App for GuiApp {
                         }
                     }
                     '\x08' => {
-                        if let game::GameState::Going = self.game.as_ref().unwrap().game_state {
-                            if !self.guess.is_empty() {
-                                self.guess.pop();
-                            }
-                            return
-                        }
-                        else if let game::GameState::FullString = self.game.as_ref().unwrap().game_state {
+                        if let GameState::Going = self.game_state() {
                             self.guess.pop();
-                            self.game.as_mut().unwrap().game_state = GameState::Going;
                         }
                     }
+                    keyboard::ESCAPE => {
+                        self.guess.clear();
+                    }
                     c => {
-                        if let game::GameState::Going = self.game.as_ref().unwrap().game_state {
-                            self.guess.push(c);
-                            if self.guess.len() == 5 {
-                                self.game.as_mut().unwrap().game_state = GameState::FullString;
-                                console::log_1(&"String fulled".into());
+                        if let GameState::Going = self.game_state() {
+                            if self.guess.len() < self.length() {
+                                self.guess.push(c);
                             }
                         }
                     }
                 }
             }
         });
+
+        self.toasts.show(ctx);
     }
 }
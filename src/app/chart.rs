@@ -0,0 +1,25 @@
+use egui::{Align2, FontFamily, FontId, Sense, Vec2};
+use super::metrics::*;
+
+const BAR_HEIGHT: f32 = 16.0;
+const BAR_WIDTH: f32 = 180.0;
+
+//The guess distribution as the bars of the original game: one row for each number of guesses,
+//the bar as long as its share of the most common one and the count written at its end
+pub fn histogram(ui: &mut egui::Ui, histogram: &[usize], highlight: Option<usize>) {
+    let most = histogram.iter().max().copied().unwrap_or(0).max(1);
+    let font = FontId::new(12.0, FontFamily::Name("SFM".into()));
+
+    for (i, n) in histogram.iter().enumerate() {
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(format!("{}", i + 1)).font(font.clone()));
+
+            //an empty bar still leaves room for its count
+            let width = (BAR_WIDTH * *n as f32 / most as f32).max(BAR_HEIGHT);
+            let (rect, _) = ui.allocate_exact_size(Vec2::new(width, BAR_HEIGHT), Sense::hover());
+            let color = if highlight == Some(i) { MY_GREEN } else { MY_GRAY };
+            ui.painter().rect_filled(rect, 0.0, color);
+            ui.painter().text(rect.right_center() - Vec2::new(4.0, 0.0), Align2::RIGHT_CENTER, n.to_string(), font.clone(), MY_WHITE);
+        });
+    }
+}
@@ -1,9 +1,10 @@
 use crate::state;
 use chrono::{self, DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
 
-// A game history recorder
+// A game history recorder, as the GUI kept it before the state file had room for all of it.
+// Only read now, to bring an older history over to state::Game.
 #[derive(Deserialize, Serialize)]
 pub struct Game {
     pub game: state::Game,
@@ -12,4 +13,33 @@ pub struct Game {
     pub date: DateTime<Utc>,
     pub win: bool,
     pub round: i32,
-}
\ No newline at end of file
+}
+
+impl From<Game> for state::Game {
+    fn from(old: Game) -> state::Game {
+        state::Game {
+            answer: old.game.answer.to_uppercase(),
+            date: Some(old.date),
+            seed: Some(old.seed),
+            day: Some(old.day),
+            win: Some(old.win),
+            ..old.game
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Entry {
+    Record(state::Game),
+    Old(Game),
+}
+
+//Reads a history holding records of either shape
+pub fn history<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<state::Game>, D::Error> {
+    let entries: Vec<Entry> = Vec::deserialize(deserializer)?;
+    Ok(entries.into_iter().map(|e| match e {
+        Entry::Record(g) => g,
+        Entry::Old(g) => g.into(),
+    }).collect())
+}
@@ -1,45 +1,58 @@
 use crate::game;
 use crate::LetterState;
 
-use super::metrics;
 use super::letter;
+use super::metrics::{FLIP, FLIP_STAGGER};
 
 
 //rendering the guesses and answer
-pub fn guesses_grid(ui: &mut egui::Ui, game: &game::Game, guess: &String, diff: bool) {
-    for i in 0..metrics::ROWS {
+//board is the place of this grid among the boards tiled on the screen.
+//With animate a row flips its tiles one by one once guessed, shake moves the row being typed.
+pub fn guesses_grid(ui: &mut egui::Ui, game: &game::Game, guess: &String, diff: bool, (board, boards): (i32, i32), animate: bool, shake: f32) {
+    let columns = game.length() as i32;
+    let rows = game.max_guesses;
+    let reveal_time = FLIP + FLIP_STAGGER * (columns - 1) as f32;
+    for i in 0..rows {
+        //the reveal of a row runs from 0 to 1, it is put back to 0 at once while the row is typed
+        let reveal_id = egui::Id::new(("reveal", board, i));
         if i < game.round - 1 {
-            for j in 0..metrics::COLUMNS {
+            let reveal = if animate { ui.ctx().animate_bool_with_time(reveal_id, true, reveal_time) } else { 1.0 };
+            for j in 0..columns {
+                let flip = ((reveal * reveal_time - j as f32 * FLIP_STAGGER) / FLIP).clamp(0.0, 1.0);
                 let _guess = &game.guesses[i as usize];
                 let _char = _guess.chars().nth(j as usize).unwrap();
                 let _state = game.guesses_state[i as usize][j as usize].clone();
-                letter::letter(ui, letter::Letter {letter: Some(_char), state: _state, row: i.clone(), column: j.clone()}, false, diff);
+                letter::letter(ui, letter::Letter {letter: Some(_char), state: _state, row: i.clone(), column: j.clone(), columns, rows, board, boards, flip, shake: 0.0}, false, diff);
             }
         }
         else if i == game.round - 1{
+            if animate {
+                ui.ctx().animate_bool_with_time(reveal_id, false, 0.0);
+            }
             let mut  pos = 0;
             for _char in guess.chars() {
-                letter::letter(ui, letter::Letter{letter: Some(_char), state: game::LetterState::Unknown, row: i.clone(), column: pos.clone()}, false, diff);
+                letter::letter(ui, letter::Letter{letter: Some(_char), state: game::LetterState::Unknown, row: i.clone(), column: pos.clone(), columns, rows, board, boards, flip: 1.0, shake}, false, diff);
                 pos += 1;
             }
-            while pos < metrics::COLUMNS {
-                letter::letter(ui, letter::Letter{letter: Some(' '), state: game::LetterState::Unknown, row: i.clone(), column: pos.clone()}, false, diff);
+            while pos < columns {
+                letter::letter(ui, letter::Letter{letter: Some(' '), state: game::LetterState::Unknown, row: i.clone(), column: pos.clone(), columns, rows, board, boards, flip: 1.0, shake}, false, diff);
                 pos += 1;
             }
         }
         else {
-            for j in 0..metrics::COLUMNS {
-                letter::letter(ui, letter::Letter{letter: Some(' '), state: game::LetterState::Unknown, row: i.clone(), column: j.clone()}, false, diff);
+            for j in 0..columns {
+                letter::letter(ui, letter::Letter{letter: Some(' '), state: game::LetterState::Unknown, row: i.clone(), column: j.clone(), columns, rows, board, boards, flip: 1.0, shake: 0.0}, false, diff);
             }
         }
         
     }
 }
 
-pub fn answer_grid (ui: &mut egui::Ui, answer: &String, answer_state: [LetterState; 5], diff: bool){
+pub fn answer_grid (ui: &mut egui::Ui, answer: &String, answer_state: &[LetterState], rows: i32, diff: bool, (board, boards): (i32, i32)){
+    let columns = answer_state.len() as i32;
     let mut x = 0;
     for _char in answer.to_uppercase().chars() {
-        letter::letter(ui, letter::Letter{letter: Some(_char), state: answer_state[x as usize], row: 6, column: x.clone()}, true, diff);
+        letter::letter(ui, letter::Letter{letter: Some(_char), state: answer_state[x as usize], row: rows, column: x.clone(), columns, rows, board, boards, flip: 1.0, shake: 0.0}, true, diff);
         x += 1;
     }
 }
\ No newline at end of file
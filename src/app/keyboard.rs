@@ -1,5 +1,5 @@
 use crate::{game, GameState, LetterState, KEYBOARD_1, KEYBOARD_2, KEYBOARD_3};
-use egui::{Align2, Color32, FontFamily, InputState, Key, Modifiers, Pos2, Rect, Sense};
+use egui::{Align2, Color32, FontFamily, Id, InputState, Key, Modifiers, Pos2, Rect, Sense};
 use super::utils;
 use utils::*;
 use super::metrics;
@@ -7,6 +7,11 @@ use metrics::*;
 
 pub const ENTER: char = '\n';
 pub const BACKSPACE: char = '\x08';
+//only on the keyboard, it clears the row
+pub const ESCAPE: char = '\x1b';
+
+//seconds an on-screen key stays lit after its key is typed
+const FLASH: f64 = 0.15;
 
 //Adapted from https://github.com/abmfy/wordle/tree/master from abmfy on 2024-07-10
 //An elegant way to realize getting the keyinput and rendering at the same time by using clousure and call-back function
@@ -26,6 +31,10 @@ fn get_text_color(state: &LetterState, diff: bool) -> Color32 {
     }
 }
 
+fn states(boards: &[&game::Game], _char: char) -> Vec<LetterState> {
+    boards.iter().map(|b| *b.Letters.get(&_char).unwrap()).collect()
+}
+
 fn get_pos(_char: &char) -> (i32, i32) {
     if *_char == '\n' {
         return (2, 0)
@@ -60,7 +69,8 @@ fn get_pos(_char: &char) -> (i32, i32) {
     (-1,-1)
 }
 
-fn key_grid(ui: &mut egui::Ui, _char: &char, _state: &LetterState, (_x, _y): &(i32, i32), diff: bool ) -> bool {
+//The key is split into one stripe for each board, coloured by that board
+fn key_grid(ui: &mut egui::Ui, _char: &char, _states: &[LetterState], (_x, _y): &(i32, i32), diff: bool, flash: bool) -> bool {
     let point = get_key_start_point(ui);
 
     let mut box_size = get_button_size(ui);
@@ -92,8 +102,9 @@ fn key_grid(ui: &mut egui::Ui, _char: &char, _state: &LetterState, (_x, _y): &(i
 
     
 
-    let _fill_color = get_fill_color(_state, diff);
-    let _text_color = get_text_color(_state, diff);
+    //the text is dark only while no board knows the letter
+    let _known = _states.iter().find(|s| **s != LetterState::Unknown).unwrap_or(&LetterState::Unknown);
+    let _text_color = get_text_color(_known, diff);
 
     if(*_y == 0 && *_x == 2) {
         ui.painter().rect(rect, 4.0, Color32::GRAY, (0.0, Color32::WHITE ));
@@ -103,16 +114,26 @@ fn key_grid(ui: &mut egui::Ui, _char: &char, _state: &LetterState, (_x, _y): &(i
         ui.painter().rect(rect, 4.0, Color32::GRAY, (0.0, Color32::WHITE ));
         ui.painter().text(rect.center(), Align2::CENTER_CENTER, "BACK", egui::FontId { size: 0.3 * box_size.x, family: FontFamily::Name("SF".into()) }, _text_color);
     }
+    else if _states.len() == 1 {
+        ui.painter().rect(rect, 4.0, get_fill_color(&_states[0], diff), (0.0, Color32::WHITE ));
+        ui.painter().text(rect.center(), Align2::CENTER_CENTER, _char.to_uppercase(), egui::FontId { size: 0.8 * box_size.x, family: FontFamily::Name("SF".into()) }, _text_color);
+    }
     else {
-        ui.painter().rect(rect, 4.0, _fill_color, (0.0, Color32::WHITE ));
+        let width = rect.width() / _states.len() as f32;
+        for (k, _state) in _states.iter().enumerate() {
+            let stripe = Rect::from_min_size(Pos2 {x: rect.min.x + k as f32 * width, y: rect.min.y}, egui::Vec2 {x: width, y: rect.height()});
+            ui.painter().rect(stripe, 0.0, get_fill_color(_state, diff), (0.0, Color32::WHITE ));
+        }
         ui.painter().text(rect.center(), Align2::CENTER_CENTER, _char.to_uppercase(), egui::FontId { size: 0.8 * box_size.x, family: FontFamily::Name("SF".into()) }, _text_color);
     }
+    if flash {
+        ui.painter().rect(rect, 4.0, Color32::from_white_alpha(110), (2.0, MY_WHITE));
+    }
     response.clicked()
 }
 
-pub fn keyboard(ui: &mut egui::Ui, game: &game::Game, diff: bool) -> Option<char> {
-    let mut press:Option<char> = None;
-
+//The key on the keyboard pressed in this frame, in the same chars as the on-screen keys
+fn physical_key(input: &mut InputState) -> Option<char> {
     const KEYS: [egui::Key; 26] = [
         Key::A,
         Key::B,
@@ -141,55 +162,55 @@ pub fn keyboard(ui: &mut egui::Ui, game: &game::Game, diff: bool) -> Option<char
         Key::Y,
         Key::Z,
     ];
-    
-    for i in KEYBOARD_1.chars() {
-        if key_grid(ui, &i, game.Letters.get(&i).as_ref().unwrap(), &get_pos(&i), diff) {
-            press = Some(i.to_uppercase().next().unwrap());
+
+    for modifiers in [Modifiers::NONE, Modifiers::SHIFT] {
+        if input.consume_key(modifiers, Key::Enter) {
+            return Some(ENTER);
         }
-    }
-    for i in KEYBOARD_2.chars() {
-        if key_grid(ui, &i, game.Letters.get(&i).as_ref().unwrap(), &get_pos(&i), diff) {
-            press = Some(i.to_uppercase().next().unwrap());
+        if input.consume_key(modifiers, Key::Backspace) {
+            return Some(BACKSPACE);
+        }
+        if input.consume_key(modifiers, Key::Escape) {
+            return Some(ESCAPE);
+        }
+        for key in KEYS {
+            if input.consume_key(modifiers, key) {
+                return format!("{key:?}").chars().next();
+            }
         }
     }
-    for i in KEYBOARD_3.chars() {
-        if key_grid(ui, &i, game.Letters.get(&i).as_ref().unwrap(), &get_pos(&i), diff) {
+    None
+}
+
+//Every board given colours the keys, a single game passes just itself
+pub fn keyboard(ui: &mut egui::Ui, boards: &[&game::Game], diff: bool) -> Option<char> {
+    let mut press:Option<char> = None;
+
+    //the keys typed into a text field of the side panel are not the game's
+    let typed = if ui.ctx().wants_keyboard_input() { None } else { ui.input_mut(physical_key) };
+
+    //the on-screen key of the last key typed is lit up for a moment, the time kept over the frames
+    let now = ui.input(|i| i.time);
+    let flash_id = Id::new("key_flash");
+    if let Some(c) = typed {
+        ui.data_mut(|d| d.insert_temp(flash_id, (c.to_ascii_lowercase(), now)));
+    }
+    let flash = ui.data(|d| d.get_temp::<(char, f64)>(flash_id)).filter(|(_, t)| now - t < FLASH).map(|(c, _)| c);
+    if flash.is_some() {
+        ui.ctx().request_repaint_after(std::time::Duration::from_secs_f64(FLASH));
+    }
+
+    for i in KEYBOARD_1.chars().chain(KEYBOARD_2.chars()).chain(KEYBOARD_3.chars()) {
+        if key_grid(ui, &i, &states(boards, i), &get_pos(&i), diff, flash == Some(i)) {
             press = Some(i.to_uppercase().next().unwrap());
         }
     }
-    if key_grid(ui, &'\n', &LetterState::Unknown, &get_pos(&'\n'), diff) {
-        press = Some('\n');
+    if key_grid(ui, &ENTER, &[LetterState::Unknown], &get_pos(&ENTER), diff, flash == Some(ENTER)) {
+        press = Some(ENTER);
     }
-    if key_grid(ui, &'\x08', &LetterState::Unknown, &get_pos(&'\x08'), diff) {
-        press = Some('\x08');
+    if key_grid(ui, &BACKSPACE, &[LetterState::Unknown], &get_pos(&BACKSPACE), diff, flash == Some(BACKSPACE)) {
+        press = Some(BACKSPACE);
     }
 
-    ui.input_mut(|input: &mut InputState| {
-        if input.consume_key(Modifiers::NONE, Key::Enter) {
-            press = Some('\n');
-        }
-        else if input.consume_key(Modifiers::NONE, Key::Backspace) {
-            press = Some('\x08');
-        }
-        else if input.consume_key(Modifiers::SHIFT, Key::Enter) {
-            press = Some('\n');
-        }
-        else if input.consume_key(Modifiers::SHIFT, Key::Backspace) {
-            press = Some('\x08');
-        }
-        else {
-            for key in KEYS {
-                if input.consume_key(Modifiers::NONE, key) {
-                    press = format!("{key:?}").chars().nth(0);
-                    break;
-                }
-                if input.consume_key(Modifiers::SHIFT, key) {
-                    press = format!("{key:?}").chars().nth(0);
-                    break;
-                }
-            }
-        }
-    });
-    
-    press
-}
\ No newline at end of file
+    press.or(typed)
+}
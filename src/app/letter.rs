@@ -14,6 +14,14 @@ pub struct Letter {
     pub state: LetterState,
     pub row: i32,
     pub column: i32,
+    pub columns: i32,
+    pub rows: i32,
+    pub board: i32,
+    pub boards: i32,
+    //how far the tile has flipped, from 0 to 1, a still tile is at 1
+    pub flip: f32,
+    //moving the tile sideways, in boxes
+    pub shake: f32,
 }
 
 impl Letter {
@@ -63,14 +71,24 @@ impl Letter {
 //Adapted from https://github.com/abmfy/wordle/tree/master from abmfy on 2024-07-10
 //An elegant way to realize getting the keyinput and rendering at the same time by using clousure and call-back function
 //The measuring part was simplified, while the idea of pixels differences in different devices is important.
-pub fn letter(ui: &mut egui::Ui, _letter: Letter, dark: bool, diff: bool) {
+pub fn letter(ui: &mut egui::Ui, mut _letter: Letter, dark: bool, diff: bool) {
     
     let point = get_start_point(ui);
 
-    let box_size = get_box_size(ui);
+    //several boards are tiled at most BOARDS_PER_ROW a row
+    let per_row = _letter.boards.min(BOARDS_PER_ROW);
+    let board_rows = (_letter.boards + per_row - 1) / per_row;
+    let tiled = if _letter.boards > 1 { (2.0 / (per_row + 1) as f32).min(1.0 / board_rows as f32) } else { 1.0 };
 
-    let x = point.x + (_letter.column as f32 - 2.5) * box_size.x;
-    let mut y = point.y + (_letter.row as f32 - 2.5) * box_size.y;
+    //shrink the boxes when there are more rows than the layout is measured for
+    let box_size = get_box_size(ui) * (ROWS as f32 / _letter.rows.max(ROWS) as f32) * tiled;
+
+    //the center of this board, a gap of one box between the boards
+    let board_x = ((_letter.board % per_row) as f32 - 0.5 * (per_row - 1) as f32) * (_letter.columns + 1) as f32 * box_size.x;
+    let board_y = ((_letter.board / per_row) as f32 - 0.5 * (board_rows - 1) as f32) * (_letter.rows as f32 + 1.5) * box_size.y;
+
+    let x = point.x + board_x + (_letter.column as f32 - 0.5 * _letter.columns as f32 + _letter.shake) * box_size.x;
+    let mut y = point.y + board_y + (_letter.row as f32 - 0.5 * (_letter.rows - 1) as f32) * box_size.y;
 
     if dark {
         y = y + 0.35 * box_size.y;
@@ -83,11 +101,20 @@ pub fn letter(ui: &mut egui::Ui, _letter: Letter, dark: bool, diff: bool) {
 
     ui.allocate_rect(rect, Sense::hover());
 
+    //a flipping tile closes to a line and opens again, its colour turned over in the middle
+    let rect = Rect::from_center_size(rect.center(), Vec2 {x: rect.width(), y: rect.height() * (1.0 - 2.0 * _letter.flip).abs()});
+    if _letter.flip < 0.5 {
+        _letter.state = LetterState::Unknown;
+    }
+    let edgewise = _letter.flip > 0.25 && _letter.flip < 0.75;
+
     let _fill_color = _letter.get_fill_color(diff);
     let _stroke_color = _letter.get_stroke_color(dark, diff);
     let _text_color = _letter.get_text_color(dark, diff);
 
     ui.painter().rect(rect, 0.0, _fill_color, (2.0, _stroke_color ));
-    ui.painter().text(rect.center(), Align2::CENTER_CENTER, _letter.letter.unwrap_or(' '), egui::FontId { size: 0.8 * box_size.x, family: FontFamily::Name("SF".into()) }, _text_color);
+    if !edgewise {
+        ui.painter().text(rect.center(), Align2::CENTER_CENTER, _letter.letter.unwrap_or(' '), egui::FontId { size: 0.8 * box_size.x, family: FontFamily::Name("SF".into()) }, _text_color);
+    }
 
 }
\ No newline at end of file
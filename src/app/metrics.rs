@@ -2,8 +2,18 @@ use egui::{Color32};
 
 //some comman parameters
 
+//the board is measured for six rows, and shrinks when more guesses are allowed
 pub const ROWS: i32 = 6;
-pub const COLUMNS: i32 = 5;
+
+//multi-board games put at most this many boards side by side
+pub const BOARDS_PER_ROW: i32 = 4;
+
+//seconds a tile takes to flip, and between the flips of two tiles of a row
+pub const FLIP: f32 = 0.4;
+pub const FLIP_STAGGER: f32 = 0.25;
+//seconds a rejected row shakes, and how far it goes in boxes
+pub const SHAKE: f32 = 0.4;
+pub const SHAKE_WIDTH: f32 = 0.12;
 
 //Colors measuring from https://www.nytimes.com/games/wordle/index.html
 pub const MY_YELLOW: Color32 = Color32::from_rgb(197, 180, 101);
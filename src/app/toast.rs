@@ -0,0 +1,47 @@
+use egui::{Align2, Color32, FontFamily, Frame, RichText, Vec2};
+
+//seconds a toast stays on the screen, the last part of it fading out
+const TOAST_TIME: f64 = 1.5;
+const FADE_TIME: f64 = 0.3;
+
+//Short messages over the board, the newest on top, each gone after a while
+#[derive(Default)]
+pub struct Toasts {
+    list: Vec<(String, f64)>,
+}
+
+impl Toasts {
+
+    pub fn add(&mut self, ctx: &egui::Context, text: String) {
+        let now = ctx.input(|i| i.time);
+        self.list.insert(0, (text, now));
+        ctx.request_repaint();
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) {
+        let now = ctx.input(|i| i.time);
+        self.list.retain(|(_, since)| now - since < TOAST_TIME);
+        if self.list.is_empty() {
+            return;
+        }
+
+        egui::Area::new(egui::Id::new("toasts"))
+            .anchor(Align2::CENTER_TOP, Vec2::new(0.0, 60.0))
+            .interactable(false)
+            .show(ctx, |ui| {
+                for (text, since) in &self.list {
+                    let left = TOAST_TIME - (now - since);
+                    let alpha = (left / FADE_TIME).min(1.0) as f32;
+                    Frame::none()
+                        .fill(Color32::BLACK.gamma_multiply(0.85 * alpha))
+                        .rounding(4.0)
+                        .inner_margin(Vec2::new(12.0, 8.0))
+                        .show(ui, |ui| {
+                            ui.label(RichText::new(text).family(FontFamily::Name("SFM".into())).size(16.0)
+                                .color(Color32::WHITE.gamma_multiply(alpha)));
+                        });
+                }
+            });
+        ctx.request_repaint();
+    }
+}
@@ -1,4 +1,7 @@
-use clap::{builder::Str, Parser};
+use clap::{builder::Str, Parser, Subcommand, ValueEnum};
+use clap::builder::TypedValueParser;
+
+use wordle::solver::Strategy;
 
 #[derive(Parser, Debug, Default, Clone)]
 #[command(name = "Wordle", version = "4.5.8", about = "Setting the parameters of the game", long_about = None)]
@@ -6,42 +9,166 @@ use clap::{builder::Str, Parser};
 pub struct Args {
 
     ///Specify the answer
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     pub word: Option<String>,
     
     ///Random word mode
-    #[arg(short, long, conflicts_with = "word")]
+    #[arg(short, long, conflicts_with = "word", global = true)]
     pub random: bool,
     
     ///Difficult modd
-    #[arg(short = 'D', long)]
+    #[arg(short = 'D', long, global = true)]
     pub difficult: bool,
     
     ///Print review after each game
     #[arg(short = 't', long)]
     pub stats: bool,
 
+    ///Add the current and best win streaks to the review
+    #[arg(long)]
+    pub streak: bool,
+
     ///Specify the day
-    #[arg(short, long, conflicts_with = "word")]
+    #[arg(short, long, conflicts_with = "word", global = true)]
     pub day: Option<u64>,
 
     ///Specify the seed
-    #[arg(short, long, conflicts_with = "word")]
+    #[arg(short, long, conflicts_with = "word", global = true)]
     pub seed: Option<u64>,
 
     ///Specify the final-set file path
-    #[arg(short = 'f', long = "final-set")]
+    #[arg(short = 'f', long = "final-set", global = true)]
     pub final_set: Option<String>,
 
     ///Specify the acceptable file path
-    #[arg(short = 'a', long = "acceptable-set")]
+    #[arg(short = 'a', long = "acceptable-set", global = true)]
     pub acceptable_set: Option<String>,
 
+    ///Print the spoiler-free result grid after each game
+    #[arg(long)]
+    pub share: bool,
+
+    ///Use orange and blue instead of green and yellow in the result grid
+    #[arg(long = "high-contrast", requires = "share")]
+    pub high_contrast: bool,
+
+    ///Play as this player, each one has its own games in the state file
+    #[arg(long, global = true)]
+    pub player: Option<String>,
+
     ///Read and save this game to specified file path in json
-    #[arg(short = 'S', long = "state")]
+    #[arg(short = 'S', long = "state", global = true)]
     pub state: Option<String>,
 
+    ///Let the word-sets hold words beyond the builtin lists
+    #[arg(long = "allow-external-words", global = true)]
+    pub allow_external_words: bool,
+
+    ///Specify the word length, from 4 to 8
+    #[arg(short, long, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(4..=8), global = true)]
+    pub length: Option<usize>,
+
+    ///Specify the maximum number of guesses
+    #[arg(short, long = "max-guesses", value_parser = clap::value_parser!(i32).range(1..), global = true)]
+    pub max_guesses: Option<i32>,
+
+    ///Play the puzzle of today, the same for everyone
+    #[arg(long, conflicts_with_all = ["word", "day", "seed", "adversarial"], global = true)]
+    pub daily: bool,
+
+    ///The date of the first daily puzzle, as YYYY-MM-DD
+    #[arg(long, requires = "daily", global = true)]
+    pub epoch: Option<chrono::NaiveDate>,
+
+    ///Absurdle mode: no fixed answer, the game dodges every guess as long as it can
+    #[arg(short = 'A', long, conflicts_with_all = ["word", "random"], global = true)]
+    pub adversarial: bool,
+
+    ///Play 2, 4 or 8 boards at once with one stream of guesses
+    #[arg(short, long, conflicts_with = "adversarial", value_parser = clap::builder::PossibleValuesParser::new(["2", "4", "8"]).map(|s| s.parse::<usize>().unwrap()))]
+    pub boards: Option<usize>,
+
+    ///Open the graphical board in a window instead of playing in the terminal
+    #[arg(long)]
+    pub gui: bool,
+
     ///Specify a config file for this game
-    #[arg(short, long)]
-    pub config: Option<String>
+    #[arg(short, long, global = true)]
+    pub config: Option<String>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum Command {
+
+    ///Let the builtin solver play the game
+    Solve {
+        ///How the solver ranks the guesses
+        #[arg(long, value_enum, default_value = "entropy")]
+        strategy: Strategy,
+
+        ///Specify the first guess instead of computing it
+        #[arg(long)]
+        start: Option<String>,
+    },
+
+    ///Let the solver play every answer in the final-set and report how it did
+    Bench {
+        ///How the solver ranks the guesses
+        #[arg(long, value_enum, default_value = "entropy")]
+        strategy: Strategy,
+
+        ///Specify the first guess instead of computing it
+        #[arg(long)]
+        start: Option<String>,
+
+        ///Only play this many answers, sampled with the seed
+        #[arg(long)]
+        sample: Option<usize>,
+
+        ///Number of threads, all the cores by default
+        #[arg(long)]
+        threads: Option<usize>,
+
+        ///Output format of the report
+        #[arg(long, value_enum, default_value = "text")]
+        format: Format,
+    },
+
+    ///Play a game of the state file again, guess by guess
+    Replay {
+        ///The number of the game in the state file, from 1
+        #[arg(short, long, value_parser = clap::value_parser!(u64).range(1..))]
+        game: u64,
+    },
+
+    ///Upgrade the state file to the version this build writes
+    ///
+    ///A state file written by an older build stays at version 1 when games are saved into it,
+    ///unless a --player other than the default one saves there. Until it is migrated,
+    ///the date, seed, day, difficulty, max guesses, word-sets and outcome of each game are not kept.
+    Migrate,
+
+    ///Full statistics of the games in the state file
+    Stats {
+        ///Output format of the report
+        #[arg(long, value_enum, default_value = "text")]
+        format: Format,
+    },
+
+    ///Rank the players of the state file
+    Leaderboard {
+        ///Output format of the ranking
+        #[arg(long, value_enum, default_value = "text")]
+        format: Format,
+    },
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
 }
@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::thread;
+
+use serde::Serialize;
+
+use crate::game::{Game, GameState};
+use crate::solver::{Solver, Strategy};
+use crate::words_gen::Words;
+
+//How the solver did on one answer
+#[derive(Clone, Serialize)]
+pub struct Record {
+    pub answer: String,
+    pub guesses: Vec<String>,
+    pub win: bool,
+}
+
+#[derive(Serialize)]
+pub struct Report {
+    pub start: String,
+    pub strategy: Strategy,
+    pub difficult: bool,
+    pub max_guesses: i32,
+    //distribution[i] is the number of wins in i + 1 guesses
+    pub distribution: Vec<usize>,
+    //average guesses of the won games
+    pub average: f64,
+    pub failures: Vec<String>,
+    pub worst: Vec<Record>,
+    pub games: Vec<Record>,
+}
+
+//how many games are listed as the worst cases
+const WORST_LIST: usize = 10;
+
+//The solver's decisions, keyed by the guesses and feedback seen so far.
+//Games sharing the same history share the same next guess, so it is only worked out once.
+type Memo = Mutex<HashMap<String, String>>;
+
+fn history_key(game: &Game) -> String {
+    game.guesses.iter().zip(&game.guesses_state)
+        .map(|(g, s)| format!("{}:{}", g, s.iter().map(|x| x.to_state()).collect::<String>()))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+//Play one game with the solver to the end
+fn play(mut game: Game, strategy: Strategy, start: &str, memo: &Memo) -> Record {
+    let mut solver = Solver::new(game.word_bank(), strategy);
+    let mut guess = start.to_string();
+
+    loop {
+        let result = match game.guess(&guess) {
+            Ok(r) => r,
+            Err(_) => panic!("The solver played a guess the game does not accept: {}", guess.to_uppercase()),
+        };
+        solver.update(&guess, &result.states);
+
+        if result.game_state != GameState::Going {
+            break;
+        }
+
+        let key = history_key(&game);
+        let known = memo.lock().unwrap().get(&key).cloned();
+        guess = match known {
+            Some(g) => g,
+            None => {
+                let g = solver.best_guess(&game).expect("No candidate left for the solver");
+                memo.lock().unwrap().insert(key, g.clone());
+                g
+            }
+        };
+    }
+
+    Record {
+        answer: game.answer.to_uppercase(),
+        win: game.game_state == GameState::Win,
+        guesses: game.guesses,
+    }
+}
+
+//Let the solver play every answer given, spread over the threads.
+//Without a start word the solver works out its own opening once for all the games.
+//The caller makes sure the answers are not empty and the start word is in ACCEPTABLE.
+pub fn run(word_bank: &Words,
+    answers: &[String],
+    strategy: Strategy,
+    start: Option<String>,
+    difficult: bool,
+    max_guesses: i32,
+    threads: usize) -> Report {
+
+    let new_game = |answer: &str| {
+        match Game::new(answer, word_bank.clone(), difficult, max_guesses) {
+            Ok(g) => g,
+            Err(_) => panic!("{} is not in the final-set", answer.to_uppercase()),
+        }
+    };
+
+    let start = match start {
+        Some(s) => s.to_lowercase(),
+        None => {
+            let first = new_game(&answers[0]);
+            Solver::new(word_bank, strategy).best_guess(&first).expect("No candidate for the solver")
+        }
+    };
+
+    let memo: Memo = Mutex::new(HashMap::new());
+    let chunk = answers.len().div_ceil(threads.max(1)).max(1);
+
+    let games: Vec<Record> = thread::scope(|scope| {
+        let handles: Vec<_> = answers.chunks(chunk).map(|part| {
+            let (memo, start, new_game) = (&memo, &start, &new_game);
+            scope.spawn(move || {
+                part.iter().map(|a| play(new_game(a), strategy, start, memo)).collect::<Vec<Record>>()
+            })
+        }).collect();
+
+        handles.into_iter().flat_map(|h| h.join().unwrap()).collect()
+    });
+
+    let mut distribution = vec![0; max_guesses as usize];
+    let mut failures = Vec::new();
+    let mut rounds = 0;
+    for g in &games {
+        if g.win {
+            distribution[g.guesses.len() - 1] += 1;
+            rounds += g.guesses.len();
+        }
+        else {
+            failures.push(g.answer.clone());
+        }
+    }
+    let wins = games.len() - failures.len();
+    let average = if wins == 0 { 0.0 } else { rounds as f64 / wins as f64 };
+
+    //failures first, then the longest wins
+    let mut worst = games.clone();
+    worst.sort_by_key(|g| (g.win, std::cmp::Reverse(g.guesses.len())));
+    worst.truncate(WORST_LIST);
+
+    Report { start: start.to_uppercase(), strategy, difficult, max_guesses, distribution, average, failures, worst, games }
+}
+
+impl Report {
+
+    //One line for each game: answer, number of guesses, result and the guesses
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("answer,guesses,win,path\n");
+        for g in &self.games {
+            csv.push_str(&format!("{},{},{},{}\n", g.answer, g.guesses.len(), g.win, g.guesses.join(" ")));
+        }
+        csv
+    }
+}
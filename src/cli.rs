@@ -0,0 +1,589 @@
+use std::io::{self, Write};
+
+use wordle::bench::Report;
+use wordle::game::*;
+use wordle::multi::MultiGame;
+use wordle::solver::{Solver, Strategy};
+use wordle::stats::{Standing, Stats};
+
+//how many of the answers left a hint lists
+const HINT_LIST: usize = 10;
+//how many starting words and answers the statistics list
+const STATS_LIST: usize = 5;
+use wordle::words_gen::Words;
+use wordle::{error, state};
+
+//The terminal front end: reading from stdin and printing, while the game itself is left to the engine.
+
+fn color_print(c: char, color: &LetterState) {
+    match color {
+        LetterState::Green => print!("{}",console::style(c.to_uppercase()).green()),
+
+        LetterState::Red => print!("{}",console::style(c.to_uppercase()).red()),
+
+        LetterState::Yellow => print!("{}",console::style(c.to_uppercase()).yellow()),
+
+        LetterState::Unknown => print!("{}",console::style(c.to_uppercase())),
+    }
+}
+
+//Print the error the way a command line tool does and leave with its own exit code
+pub fn exit_on_error(e: wordle::error::Error) -> ! {
+    eprintln!("{} {}", console::style("error:").red().bold(), e);
+    std::process::exit(e.exit_code());
+}
+
+pub fn clear_screen() {
+    print!("\x1B[2J\x1B[1;1H");
+    io::stdout().flush().unwrap();
+}
+
+//Exam if the args.word is passed into game
+//if not and not in random, get an answer from stdout.
+pub fn receive_answer(word_bank: &mut Words,
+    random: bool,
+    day: u64,
+    seed: u64,
+    word: &Option<String>,
+    is_tty: bool) -> String {
+
+    if random {
+        return word_bank.pick_answer(seed, day).unwrap_or_else(|e| exit_on_error(e));
+    }
+
+    if let Some(_answer) = word {
+        return _answer.to_lowercase();
+    }
+
+    let mut answer: String = String::new();
+    //this place has a fallback of input nothing.
+    if is_tty {
+        print!("Please specify a {} {}: ",
+                console::style(format!("{}-letter", word_bank.length)).blue().bold(),
+                console::style("answer").red().bold());
+        loop {
+            io::stdout().flush().unwrap();
+            answer = String::new();
+            io::stdin().read_line(&mut answer).expect("Wrong input");
+            answer = answer.trim().to_string();
+
+            if !word_bank.FINAL.contains(&answer.to_lowercase()) {
+                print!("{}, please specify again: ", console::style("Answer not in the word bank FINAL").red());
+                io::stdout().flush().unwrap();
+                continue;
+            }
+            else {
+                break;
+            }
+        }
+    }
+    else {
+        io::stdin().read_line(&mut answer).expect("Wrong input");
+        answer = answer.trim().to_string();
+    }
+    answer.to_lowercase()
+}
+
+//The answers of a multi-board game: picked by the seed and day, given with --word
+//as a comma separated list, or read from stdin one a line.
+pub fn receive_answers(word_bank: &mut Words,
+    random: bool,
+    day: u64,
+    seed: u64,
+    word: &Option<String>,
+    is_tty: bool,
+    boards: usize) -> Vec<String> {
+
+    if random {
+        return word_bank.pick_answers(seed, day, boards).unwrap_or_else(|e| exit_on_error(e));
+    }
+
+    let answers: Vec<String> = match word {
+        Some(w) => w.split(',').map(|s| s.trim().to_lowercase()).collect(),
+        None => (0..boards).map(|_| receive_answer(word_bank, false, day, seed, &None, is_tty)).collect(),
+    };
+
+    if answers.len() != boards {
+        exit_on_error(error::Error::AnswerCount { expected: boards, found: answers.len() });
+    }
+    answers
+}
+
+//Entry of each game.
+pub fn game_on(game: &mut Game, is_tty: bool) -> Result<(Vec<String>, i32), Error>{
+
+    if is_tty{
+        print_all(game);
+    }
+
+    loop {
+
+        if is_tty {
+            println!("{} : {} / {}", console::style("Round").green(), console::style(game.round).blue(), game.max_guesses);
+            print!("Make a guess: ");
+            io::stdout().flush().unwrap();
+        }
+
+        let mut guess: String = String::new();
+        io::stdin().read_line(&mut guess).expect("Wrong input");
+        let guess = guess.trim().to_string();
+
+        if is_tty && (guess == "?" || guess == ":hint") {
+            print_hint(game);
+            continue;
+        }
+
+        match game.guess(&guess) {
+
+            Ok(result) => {
+                if is_tty {
+                    print_all(game);
+                }
+                else {
+                    print_state(game, &result.states);
+                }
+
+                match result.game_state {
+                    GameState::Win => {
+                        let round = game.round - 1;
+                        if is_tty {
+                            println!("{} {} {}", console::style("CORRECT").yellow(), console::style(game.answer.to_uppercase()).green(), round);
+                        }
+                        else {
+                            println!("CORRECT {}", round);
+                        }
+                        return Ok((game.guesses.clone(), round));
+                    }
+
+                    GameState::Lose => {
+                        if is_tty {
+                            print!("{} ", console::style("FAILED").red());
+                            let mut pos = 0;
+                            for i in game.answer.chars() {
+                                color_print(i, &game.answer_state[pos]);
+                                pos += 1;
+                            }
+                            io::stdout().flush().unwrap();
+                            println!("");
+                        }
+                        else {
+                            println!("FAILED {}", game.answer.to_uppercase());
+                        }
+                        return Err(Error::GameLose(game.guesses.clone()));
+                    }
+
+                    GameState::Going => {}
+                }
+            }
+
+            Err(Error::NotInWordList) => {
+                if is_tty {
+                    println!("{}",console::style("The word is not in word list, try once more.").red());
+                    io::stdout().flush().unwrap();
+                }
+                else {
+                    println!("INVALID");
+                }
+            }
+
+            Err(Error::DisobeyingDifficult(violation)) => {
+                if is_tty {
+                    println!("{}",console::style(format!("{}, try once more.", violation)).red());
+                    io::stdout().flush().unwrap();
+                }
+                else {
+                    println!("INVALID");
+                }
+            }
+
+            //the loop is left once the game ends, and Game::guess scores a wrong answer as feedback,
+            //so these only come back if the game was over before it began
+            Err(e @ (Error::GameOver | Error::WrongAnswer | Error::GameLose(_))) => return Err(e),
+        }
+    }
+}
+
+//Entry of each multi-board game, the same outcome as game_on
+pub fn multi_on(game: &mut MultiGame, is_tty: bool) -> Result<(Vec<String>, i32), Error> {
+
+    if is_tty {
+        print_boards(game);
+    }
+
+    loop {
+
+        if is_tty {
+            println!("{} : {} / {}", console::style("Round").green(), console::style(game.round).blue(), game.max_guesses);
+            print!("Make a guess: ");
+            io::stdout().flush().unwrap();
+        }
+
+        let mut guess: String = String::new();
+        io::stdin().read_line(&mut guess).expect("Wrong input");
+        let guess = guess.trim().to_string();
+
+        //the hint is for the first board not solved yet
+        if is_tty && (guess == "?" || guess == ":hint") {
+            if let Some(board) = game.boards.iter_mut().find(|b| b.game_state == GameState::Going) {
+                print_hint(board);
+            }
+            continue;
+        }
+
+        match game.guess(&guess) {
+
+            Ok(results) => {
+                if is_tty {
+                    print_boards(game);
+                }
+                else {
+                    //one line a board, the boards already solved show dashes
+                    for (board, result) in game.boards.iter().zip(&results) {
+                        match result {
+                            Some(r) => print_state(board, &r.states),
+                            None => print_state(board, &[]),
+                        }
+                    }
+                }
+
+                match game.game_state {
+                    GameState::Win => {
+                        let round = game.round - 1;
+                        if is_tty {
+                            println!("{} {} {}", console::style("CORRECT").yellow(), console::style(answers_upper(game)).green(), round);
+                        }
+                        else {
+                            println!("CORRECT {}", round);
+                        }
+                        return Ok((game.guesses.clone(), round));
+                    }
+
+                    GameState::Lose => {
+                        if is_tty {
+                            println!("{} {}", console::style("FAILED").red(), answers_upper(game));
+                        }
+                        else {
+                            println!("FAILED {}", answers_upper(game));
+                        }
+                        return Err(Error::GameLose(game.guesses.clone()));
+                    }
+
+                    GameState::Going => {}
+                }
+            }
+
+            Err(Error::NotInWordList) => {
+                if is_tty {
+                    println!("{}",console::style("The word is not in word list, try once more.").red());
+                    io::stdout().flush().unwrap();
+                }
+                else {
+                    println!("INVALID");
+                }
+            }
+
+            Err(Error::DisobeyingDifficult(violation)) => {
+                if is_tty {
+                    println!("{}",console::style(format!("{}, try once more.", violation)).red());
+                    io::stdout().flush().unwrap();
+                }
+                else {
+                    println!("INVALID");
+                }
+            }
+
+            //the loop is left once the game ends, and MultiGame::guess scores a wrong answer as feedback,
+            //so these only come back if the game was over before it began
+            Err(e @ (Error::GameOver | Error::WrongAnswer | Error::GameLose(_))) => return Err(e),
+        }
+    }
+}
+
+fn answers_upper(game: &MultiGame) -> String {
+    game.answers().iter().map(|a| a.to_uppercase()).collect::<Vec<String>>().join(" ")
+}
+
+//Show how many answers are still possible, a few of them and the best next guess.
+//The hint is counted into the game.
+fn print_hint(game: &mut Game) {
+    game.hints += 1;
+
+    let solver = Solver::from_game(game, Strategy::Entropy);
+    println!("{} {} answers left", console::style("Hint:").yellow(), console::style(solver.candidates.len()).blue());
+
+    let listed: Vec<String> = solver.candidates.iter().take(HINT_LIST).map(|w| w.to_uppercase()).collect();
+    if solver.candidates.len() > HINT_LIST {
+        println!("{} ...", listed.join(" "));
+    }
+    else {
+        println!("{}", listed.join(" "));
+    }
+
+    if let Some(best) = solver.best_guess(game) {
+        println!("{} {}", console::style("Try:").yellow(), console::style(best.to_uppercase()).green());
+    }
+    io::stdout().flush().unwrap();
+}
+
+//Let the solver play the game, printing each guess with its feedback
+//and the number of candidates left after it.
+pub fn solve(game: &mut Game, strategy: Strategy, start: Option<String>, is_tty: bool) {
+
+    let mut solver = Solver::new(game.word_bank(), strategy);
+    let mut next = start.map(|s| s.to_lowercase());
+    if let Some(s) = &next {
+        if !game.word_bank().ACCEPTABLE.contains(s) {
+            exit_on_error(error::Error::GuessNotInList(s.clone()));
+        }
+    }
+
+    loop {
+        let guess = match next.take() {
+            Some(g) => g,
+            None => solver.best_guess(game).expect("No candidate left for the solver"),
+        };
+
+        let result = match game.guess(&guess) {
+            Ok(r) => r,
+            Err(_) => exit_on_error(error::Error::GuessNotInList(guess)),
+        };
+        solver.update(&guess, &result.states);
+
+        let pattern: String = result.states.iter().map(|s| s.to_state()).collect();
+        if is_tty {
+            for (c, state) in guess.chars().zip(&result.states) {
+                color_print(c, state);
+            }
+        }
+        else {
+            print!("{}", guess.to_uppercase());
+        }
+        println!(" {} {}", pattern, solver.candidates.len());
+
+        match result.game_state {
+            GameState::Win => {
+                println!("CORRECT {}", game.round - 1);
+                return;
+            }
+            GameState::Lose => {
+                println!("FAILED {}", game.answer.to_uppercase());
+                return;
+            }
+            GameState::Going => {}
+        }
+    }
+}
+
+//Score the guesses of a recorded game through the engine again and print each row as it was played.
+//A multi-board game shows its boards side by side, the boards already solved are left blank.
+pub fn replay(record: &state::Game, words: Words, is_tty: bool) {
+    let answers = if record.answers.is_empty() { vec![record.answer.clone()] } else { record.answers.clone() };
+
+    //as many rows as the game had
+    let mut game = match MultiGame::new(&answers, words.clone(), false, record.allowed_guesses()) {
+        Ok(g) => g,
+        Err(_) => {
+            let wrong = answers.iter().find(|a| !words.FINAL.contains(&a.to_lowercase())).cloned().unwrap_or_default();
+            exit_on_error(error::Error::AnswerNotInList(wrong))
+        }
+    };
+
+    for guess in &record.guesses {
+        let results = match game.guess(guess) {
+            Ok(r) => r,
+            Err(_) => exit_on_error(error::Error::GuessNotInList(guess.clone())),
+        };
+
+        if !is_tty {
+            print!("{}", guess.to_uppercase());
+        }
+        for result in &results {
+            match (result, is_tty) {
+                (Some(r), true) => {
+                    for (c, state) in guess.chars().zip(&r.states) {
+                        color_print(c, state);
+                    }
+                }
+                (Some(r), false) => print!(" {}", r.states.iter().map(|s| s.to_state()).collect::<String>()),
+                (None, true) => print!("{}", " ".repeat(game.length())),
+                (None, false) => print!(" {}", "-".repeat(game.length())),
+            }
+            if is_tty {
+                print!(" ");
+            }
+        }
+        println!();
+    }
+
+    match game.game_state {
+        GameState::Win => println!("CORRECT {}", game.round - 1),
+        _ => println!("FAILED {}", answers_upper(&game)),
+    }
+}
+
+//A guess distribution, a line for each number of guesses.
+//The bars are scaled to at most 40 characters.
+fn print_bars(distribution: &[usize]) {
+    let most = distribution.iter().max().copied().unwrap_or(0).max(1);
+    for (i, n) in distribution.iter().enumerate() {
+        println!("{:>2} {:>5} {}", i + 1, n, "#".repeat(n * 40 / most));
+    }
+}
+
+//Print the report of a bench run in a human-readable way
+pub fn print_report(report: &Report) {
+    let wins: usize = report.distribution.iter().sum();
+
+    println!("{} {} ({:?}{})", console::style("Start").green(), report.start, report.strategy,
+        if report.difficult { ", difficult" } else { "" });
+    println!("Games: {}  Wins: {}  Failures: {}", report.games.len(), wins, report.failures.len());
+    println!("Average: {:.3}", report.average);
+
+    print_bars(&report.distribution);
+
+    if !report.failures.is_empty() {
+        println!("{} {}", console::style("Failed:").red(), report.failures.join(" "));
+    }
+    println!("{}", console::style("Worst:").yellow());
+    for g in &report.worst {
+        println!("{} {} {}", g.answer, g.guesses.len(), g.guesses.join(" "));
+    }
+}
+
+//The statistics of a state file, lists cut to the first few
+pub fn print_stats(stats: &Stats) {
+
+    println!("Games: {}  Wins: {}  Win%: {:.1}", stats.games, stats.wins, stats.win_percentage);
+    println!("Streak: {}  Max streak: {}", stats.current_streak, stats.max_streak);
+    println!("Average: {:.3}", stats.average);
+
+    print_bars(&stats.histogram);
+    println!("{:>2} {:>5}", "X", stats.games - stats.wins);
+
+    println!("{}", console::style("Starting words:").green());
+    for s in stats.starts.iter().take(STATS_LIST) {
+        println!("{} {:.2} ({} games)", s.word, s.average, s.games);
+    }
+    println!("{}", console::style("Letters:").green());
+    println!("{}", stats.letters.iter().map(|l| format!("{}:{}", l.letter, l.count)).collect::<Vec<String>>().join(" "));
+    println!("{}", console::style("Hardest answers:").yellow());
+    for a in stats.answers.iter().take(STATS_LIST) {
+        println!("{} {:.2} ({} games)", a.word, a.average, a.games);
+    }
+}
+
+//One line for each player, the best first
+pub fn print_leaderboard(board: &[Standing]) {
+    println!("{:>4} {:<12} {:>5} {:>6} {:>7} {:>6} {:>4}", "#", "Player", "Games", "Win%", "Average", "Streak", "Best");
+    for (i, s) in board.iter().enumerate() {
+        println!("{:>4} {:<12} {:>5} {:>6.1} {:>7.3} {:>6} {:>4}", i + 1, s.player, s.games, s.win_percentage,
+            s.average, s.current_streak, s.max_streak);
+    }
+}
+
+//Print the word and current states of letters.
+//An empty word is a board that took no guess and is shown as dashes.
+pub fn print_state(game: &Game, word: &[LetterState]) {
+    for i in word {
+        print!("{}", i.to_state());
+    }
+    if word.is_empty() {
+        print!("{}", "-".repeat(game.length()));
+    }
+    print!(" ");
+    for i in ALPHABET.chars() {
+        print!("{}", game.Letters.get(&i).unwrap().to_state());
+    }
+    println!("");
+}
+
+fn print_keyboard(game: &Game) {
+    for i in KEYBOARD_1.chars() {
+        color_print(i, game.Letters.get(&i).unwrap());
+    }
+    io::stdout().flush().unwrap();
+    println!("");
+
+    for i in KEYBOARD_2.chars() {
+        color_print(i, game.Letters.get(&i).unwrap());
+    }
+    io::stdout().flush().unwrap();
+    println!("");
+
+    for i in KEYBOARD_3.chars() {
+        color_print(i, game.Letters.get(&i).unwrap());
+    }
+    io::stdout().flush().unwrap();
+    println!("");
+}
+
+fn print_guesses(game: &Game) {
+    let mut x = 0;
+    for i in &game.guesses {
+        let mut y = 0;
+        for j in i.chars() {
+            color_print(j, &game.guesses_state[x][y]);
+            y += 1;
+        }
+        println!("");
+        x += 1;
+    }
+}
+
+fn print_all(game: &Game) {
+    clear_screen();
+
+    println!("{}", console::style("Wordle").bold().bright().green());
+
+    println!("");
+
+    print_guesses(game);
+
+    println!("");
+
+    print_keyboard(game);
+
+    println!("");
+}
+
+//Print the boards side by side, each with its own keyboard under it
+fn print_boards(game: &MultiGame) {
+    clear_screen();
+
+    println!("{}", console::style("Wordle").bold().bright().green());
+
+    println!("");
+
+    //every board is as wide as its keyboard
+    let gap = "   ";
+    let width = KEYBOARD_1.len().max(game.length());
+    for row in 0..game.max_guesses as usize {
+        for board in &game.boards {
+            match board.guesses.get(row) {
+                Some(guess) => {
+                    for (c, state) in guess.chars().zip(&board.guesses_state[row]) {
+                        color_print(c, state);
+                    }
+                }
+                None => print!("{}", "_".repeat(board.length())),
+            }
+            print!("{}{}", " ".repeat(width - board.length()), gap);
+        }
+        println!("");
+    }
+
+    println!("");
+
+    for keys in [KEYBOARD_1, KEYBOARD_2, KEYBOARD_3] {
+        for board in &game.boards {
+            for i in keys.chars() {
+                color_print(i, board.Letters.get(&i).unwrap());
+            }
+            print!("{}{}", " ".repeat(width - keys.len()), gap);
+        }
+        io::stdout().flush().unwrap();
+        println!("");
+    }
+
+    println!("");
+}
@@ -1,7 +1,9 @@
 use clap::builder::Str;
-use config::{Config, ConfigError, File, FileFormat};
+use config::{Config, File, FileFormat};
 use serde::{Deserialize, Serialize};
 
+use crate::error::Error;
+
 #[derive(Deserialize, Debug, Serialize, Clone)]
 pub struct GameConfig {
     
@@ -12,8 +14,19 @@ pub struct GameConfig {
     pub seed: Option<u64>,
     pub final_set: Option<String>,
     pub acceptable_set: Option<String>,
+    pub allow_external_words: Option<bool>,
     pub state: Option<String>,
-    pub word: Option<String>
+    pub player: Option<String>,
+    pub word: Option<String>,
+    pub length: Option<usize>,
+    pub max_guesses: Option<i32>,
+    pub boards: Option<usize>,
+    pub adversarial: Option<bool>,
+    pub daily: Option<bool>,
+    pub epoch: Option<String>,
+    pub share: Option<bool>,
+    pub high_contrast: Option<bool>,
+    pub streak: Option<bool>
 }
 
 //Deposed later. Write here due to the misuse of Config::builder()
@@ -27,8 +40,19 @@ impl GameConfig {
             seed: None,
             final_set: None,
             acceptable_set: None,
+            allow_external_words: None,
             state: None,
-            word: None
+            player: None,
+            word: None,
+            length: None,
+            max_guesses: None,
+            boards: None,
+            adversarial: None,
+            daily: None,
+            epoch: None,
+            share: None,
+            high_contrast: None,
+            streak: None
         }
     }
     pub fn new_with_day(day: u64) -> GameConfig {
@@ -40,8 +64,19 @@ impl GameConfig {
             seed: None,
             final_set: None,
             acceptable_set: None,
+            allow_external_words: None,
             state: None,
-            word: None
+            player: None,
+            word: None,
+            length: None,
+            max_guesses: None,
+            boards: None,
+            adversarial: None,
+            daily: None,
+            epoch: None,
+            share: None,
+            high_contrast: None,
+            streak: None
         }
     }
 
@@ -49,11 +84,13 @@ impl GameConfig {
 }
 
 //load config to GameConfig
-pub fn load_config(c: &String) -> Result<GameConfig, ConfigError> {
+pub fn load_config(c: &String) -> Result<GameConfig, Error> {
+
+    let damaged = |e: config::ConfigError| Error::ConfigDamaged { path: c.clone(), message: e.to_string() };
 
-    let mut _config = Config::builder().add_source(File::new(&c, FileFormat::Json)).build()?;
+    let mut _config = Config::builder().add_source(File::new(&c, FileFormat::Json)).build().map_err(damaged)?;
 
-    _config.try_deserialize()
+    _config.try_deserialize().map_err(damaged)
 
 }
 
@@ -0,0 +1,29 @@
+use chrono::{Duration, Local, NaiveDate};
+
+//The daily puzzle: one answer for everyone on a calendar date.
+//It is the usual random mode with a fixed seed, the day counted from an epoch.
+
+pub const SEED: u64 = 20240701;
+
+//The first daily puzzle, unless another epoch is given
+pub fn default_epoch() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, 7, 1).unwrap()
+}
+
+pub fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+//The day of the date, counting from 1 on the epoch.
+//After the last answer it starts over from the first one.
+pub fn day_of(date: NaiveDate, epoch: NaiveDate, answers: usize) -> u64 {
+    let days = (date - epoch).num_days();
+    days.rem_euclid(answers.max(1) as i64) as u64 + 1
+}
+
+//Time left until the puzzle of tomorrow, by the local clock
+pub fn until_next() -> Duration {
+    let now = Local::now().naive_local();
+    let midnight = (now.date() + Duration::days(1)).and_hms_opt(0, 0, 0).unwrap();
+    midnight - now
+}
@@ -0,0 +1,101 @@
+use std::fmt;
+
+//Everything that can go wrong before a game starts: the word-sets, the state file and the config file.
+//Each one carries where it happened, so the CLI can tell the user what to fix.
+#[derive(Debug)]
+pub enum Error {
+    FileNotExist { path: String },
+    //the file is there but could not be read, as the io error tells
+    FileUnreadable { path: String, message: String },
+    DuplicatedWords { path: String, line: usize, word: String },
+    BeyondBuiltinWords { path: String, line: usize, word: String },
+    WrongLength { path: String, line: usize, word: String },
+    WrongAlphabet { path: String, line: usize, word: String },
+    LengthOutOfRange(usize),
+    NoBuiltinWords,
+    //answers of the final-set that the acceptable-set would never let be guessed
+    UnreachableAnswers { path: String, words: Vec<String> },
+    AnswerNotInList(String),
+    //nothing in the final-set to play with
+    EmptyFinalSet,
+    //a random game's day beyond the answers of the final-set, days counted from 1
+    DayOutOfRange { day: u64, days: usize },
+    //the daily puzzle of this date is in the state file already
+    DailyPlayed(String),
+    //there are not that many games in the state file
+    GameNotInState { path: String, game: u64 },
+    //a recorded guess the word-sets given do not accept
+    GuessNotInList(String),
+    //a multi-board game was given a different number of answers than boards
+    AnswerCount { expected: usize, found: usize },
+    //the final-set has fewer answers than a random multi-board game has boards
+    TooFewAnswers { boards: usize, answers: usize },
+    StateDamaged { path: String, line: usize, message: String },
+    ConfigDamaged { path: String, message: String },
+    //the state file could not be locked or written
+    StateUnwritable { path: String, message: String },
+}
+
+impl Error {
+
+    //Exit code of the CLI, one for each kind of failure so scripts can tell them apart.
+    //1 is left to rust panics and 2 to clap's usage errors.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::FileNotExist { .. } => 3,
+            Error::DuplicatedWords { .. } => 4,
+            Error::BeyondBuiltinWords { .. } => 5,
+            Error::WrongLength { .. } => 6,
+            Error::LengthOutOfRange(_) => 19,
+            Error::NoBuiltinWords => 7,
+            Error::AnswerNotInList(_) => 8,
+            Error::StateDamaged { .. } => 9,
+            Error::ConfigDamaged { .. } => 10,
+            Error::UnreachableAnswers { .. } => 11,
+            Error::WrongAlphabet { .. } => 12,
+            Error::AnswerCount { .. } => 13,
+            Error::EmptyFinalSet => 14,
+            Error::DailyPlayed(_) => 15,
+            Error::GameNotInState { .. } => 16,
+            Error::GuessNotInList(_) => 17,
+            Error::StateUnwritable { .. } => 18,
+            Error::FileUnreadable { .. } => 20,
+            Error::DayOutOfRange { .. } => 21,
+            Error::TooFewAnswers { .. } => 22,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::FileNotExist { path } => write!(f, "{}: file does not exist", path),
+            Error::FileUnreadable { path, message } => write!(f, "{}: file not readable: {}", path, message),
+            Error::DuplicatedWords { path, line, word } =>
+                write!(f, "{}:{}: duplicated word {}", path, line, word.to_uppercase()),
+            Error::BeyondBuiltinWords { path, line, word } =>
+                write!(f, "{}:{}: {} is not in the builtin word list", path, line, word.to_uppercase()),
+            Error::WrongLength { path, line, word } =>
+                write!(f, "{}:{}: {} has the wrong length", path, line, word.to_uppercase()),
+            Error::WrongAlphabet { path, line, word } =>
+                write!(f, "{}:{}: {} has letters out of a to z", path, line, word.to_uppercase()),
+            Error::LengthOutOfRange(length) => write!(f, "word length {} is not supported", length),
+            Error::NoBuiltinWords =>
+                write!(f, "only five-letter words are builtin, please provide both --final-set and --acceptable-set"),
+            Error::UnreachableAnswers { path, words } =>
+                write!(f, "{}: answers not in the acceptable-set: {}", path,
+                    words.iter().map(|w| w.to_uppercase()).collect::<Vec<String>>().join(" ")),
+            Error::AnswerNotInList(word) => write!(f, "{} is not in the final-set", word.to_uppercase()),
+            Error::DailyPlayed(date) => write!(f, "the daily puzzle of {} has been played already", date),
+            Error::GameNotInState { path, game } => write!(f, "{}: there is no game {}", path, game),
+            Error::GuessNotInList(word) => write!(f, "{} is not in the acceptable-set", word.to_uppercase()),
+            Error::EmptyFinalSet => write!(f, "the final-set is empty"),
+            Error::DayOutOfRange { day, days } => write!(f, "day {} is not between 1 and {}", day, days),
+            Error::AnswerCount { expected, found } => write!(f, "{} answers given for {} boards", found, expected),
+            Error::TooFewAnswers { boards, answers } => write!(f, "the final-set has {} answers for {} boards", answers, boards),
+            Error::StateDamaged { path, line, message } => write!(f, "{}:{}: state file damaged: {}", path, line, message),
+            Error::ConfigDamaged { path, message } => write!(f, "{}: config file damaged: {}", path, message),
+            Error::StateUnwritable { path, message } => write!(f, "{}: state file not saved: {}", path, message),
+        }
+    }
+}
@@ -1,12 +1,7 @@
-use std::{collections::HashMap, hash::Hash, io::SeekFrom};
-use std::io::{self, Write};
-use console::{self, user_attended};
+use std::collections::HashMap;
+use std::fmt;
 use serde::{Deserialize, Serialize};
 
-
-use crate::builtin_words::*;
-use serde_json::json;
-
 use crate::words_gen;
 use words_gen::*;
 
@@ -17,7 +12,7 @@ pub const KEYBOARD_1: &str = "qwertyuiop";
 pub const KEYBOARD_2: &str = "asdfghjkl";
 pub const KEYBOARD_3: &str = "zxcvbnm";
 
-#[derive(Copy, Clone, Deserialize, Serialize)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub enum LetterState {
     Green,
     Red,
@@ -28,277 +23,207 @@ pub enum LetterState {
 pub enum Error {
     NotInWordList,
     WrongAnswer,
-    DisobeyingDifficult,
+    DisobeyingDifficult(Violation),
+    GameOver,
     GameLose(Vec<String>)
 }
 
-#[derive(Deserialize, Serialize)]
+//The hint of difficult mode a guess did not use
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Violation {
+    //a green letter moved from its place, the position counted from 0
+    Green { position: usize, letter: char },
+    //a yellow letter left out of the guess
+    Yellow { letter: char },
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Violation::Green { position, letter } => {
+                let n = position + 1;
+                let suffix = match (n % 10, n % 100) {
+                    (1, 11) | (2, 12) | (3, 13) => "th",
+                    (1, _) => "st",
+                    (2, _) => "nd",
+                    (3, _) => "rd",
+                    _ => "th",
+                };
+                write!(f, "{}{} letter must be {}", n, suffix, letter.to_ascii_uppercase())
+            }
+            Violation::Yellow { letter } => write!(f, "Guess must contain {}", letter.to_ascii_uppercase()),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Deserialize, Serialize)]
 pub enum GameState {
-    FullString,
     Going,
     Win,
     Lose,
 }
 
 impl LetterState {
-    fn to_state(&self) -> char {
+    pub fn to_state(&self) -> char {
         match self {
             Self::Green => 'G',
             Self::Red => 'R',
             Self::Yellow => 'Y',
             Self::Unknown => 'X',
-            _ => unimplemented!("Not the recording state")
         }
     }
 }
 
-fn color_print(c: char, color: &LetterState) {
-    match color {
-        LetterState::Green => print!("{}",console::style(c.to_uppercase()).green()),
+//The feedback of a guess against an answer, without touching any game.
+pub fn score(guess: &str, answer: &str) -> Vec<LetterState> {
+    let guess: Vec<char> = guess.chars().collect();
+    let answer: Vec<char> = answer.chars().collect();
+    let mut states = vec![LetterState::Red; guess.len()];
+    score_into(&guess, &answer, &mut states);
+    states
+}
 
-        LetterState::Red => print!("{}",console::style(c.to_uppercase()).red()),
+//Greens are taken first, then the letters left in the answer are handed out as yellows from left to right.
+//Works on a buffer so the solver can score lots of words without allocating.
+pub fn score_into(guess: &[char], answer: &[char], states: &mut [LetterState]) {
+    //positions of the answer already taken by a green or a yellow, words are at most MAX_LENGTH long
+    let mut used: u64 = 0;
 
-        LetterState::Yellow => print!("{}",console::style(c.to_uppercase()).yellow()),
-        
-        LetterState::Unknown => print!("{}",console::style(c.to_uppercase())),
+    for (pos, g) in guess.iter().enumerate() {
+        if answer.get(pos) == Some(g) {
+            states[pos] = LetterState::Green;
+            used |= 1 << pos;
+        }
+        else {
+            states[pos] = LetterState::Red;
+        }
+    }
+
+    for (pos, g) in guess.iter().enumerate() {
+        if states[pos] == LetterState::Green {
+            continue;
+        }
+        if let Some(k) = (0..answer.len()).find(|&k| used & (1 << k) == 0 && answer[k] == *g) {
+            used |= 1 << k;
+            states[pos] = LetterState::Yellow;
+        }
     }
 }
 
-fn clear_screen() {
-    print!("\x1B[2J\x1B[1;1H");
-    io::stdout().flush().unwrap();
+//What the engine tells the front ends after a guess is accepted
+pub struct GuessResult {
+    pub states: Vec<LetterState>,
+    pub letters: HashMap<char, LetterState>,
+    pub game_state: GameState,
 }
 
 //Adapted from https://github.com/abmfy/wordle/tree/master from abmfy on 2024-07-07
 //A referrence to the structure and struct relationships
+//The engine itself never touches stdin/stdout, the CLI and the GUI drive it through guess().
 #[derive(Deserialize, Serialize)]
 pub struct Game {
     pub answer: String,
-    pub answer_state: [LetterState; 5],
+    pub answer_state: Vec<LetterState>,
     pub guesses: Vec<String>,
-    pub guesses_state: Vec<[LetterState; 5]>,
+    pub guesses_state: Vec<Vec<LetterState>>,
     pub Letters: HashMap<char, LetterState>,
-    is_tty: bool,
     pub difficult: bool,
-    random: bool,
-    pub day: u64,
-    pub seed: u64,
-    word_bank: Words, 
+    word_bank: Words,
     pub round: i32,
+    #[serde(default = "max_guess_times")]
+    pub max_guesses: i32,
+    #[serde(default)]
+    pub hints: i32,
+    //Absurdle: the answers still left to the game, empty when the answer is fixed
+    #[serde(default)]
+    pub pool: Vec<String>,
     pub game_state: GameState,
 }
 
+fn max_guess_times() -> i32 {
+    MAX_GUESS_TIMES
+}
+
 impl Game {
 
-    //Initializing an object of Game
-    pub fn new(_random: bool, 
-        _difficult: bool,
-        _day: Option<u64>,
-        _seed: Option<u64>,
-        _is_tty: bool,
-        _f_set: Option<String>,
-        _a_set: Option<String>) -> Game {
+    //Initializing a game with a fixed answer, which must be in the FINAL of the bank
+    pub fn new(answer: &str, word_bank: Words, difficult: bool, max_guesses: i32) -> Result<Game, Error> {
+
+        let answer = answer.to_lowercase();
+        if !word_bank.FINAL.contains(&answer) {
+            return Err(Error::NotInWordList);
+        }
 
         let mut _letters: HashMap<char, LetterState> = HashMap::new();
         for i in ALPHABET.chars() {
             _letters.insert(i, LetterState::Unknown);
         }
 
-        let mut _words = Words::new();
-        if let Some(f) = _f_set {
-            match _words.FINAL_set(f) {
-                Ok(_) => {}
-                Err(words_gen::Error::DuplicatedWords) => panic!("Duplicated words in the final-set provided"),
-                Err(words_gen::Error::BeyondBuiltinWords) => panic!("Final-set provided reach beyond the original set"),
-                Err(words_gen::Error::FileNotExist) => panic!("File does not exist"),
-            }
-        }
-        if let Some(a) = _a_set {
-            match _words.ACCEPTABLE_set(a) {
-                Ok(_) => {}
-                Err(words_gen::Error::DuplicatedWords) => panic!("Duplicated words in the final-set provided"),
-                Err(words_gen::Error::BeyondBuiltinWords) => panic!("Final-set provided reach beyond the original set"),
-                Err(words_gen::Error::FileNotExist) => panic!("File does not exist"),
-            }
-        }
-
-        let mut _day = _day;
-        let mut _seed = _seed;
-
-
-        Game {answer: String::new(), 
-            answer_state:[LetterState::Unknown; 5], 
-            guesses: Vec::<String>::new(), 
+        Ok(Game {answer,
+            answer_state: vec![LetterState::Unknown; word_bank.length],
+            guesses: Vec::<String>::new(),
             guesses_state: Vec::new(),
-            Letters: _letters, 
-            is_tty: _is_tty, 
-            difficult: _difficult, 
-            random: _random,
-            day: _day.unwrap_or(1),
-            seed: _seed.unwrap_or(2024),
-            word_bank: _words,
+            Letters: _letters,
+            difficult,
+            word_bank,
             round: 1,
-            game_state: GameState::Going}
+            max_guesses,
+            hints: 0,
+            pool: Vec::new(),
+            game_state: GameState::Going})
     }
 
-    //Exam if the args.word is passed into game
-    //if not and not in random, get an answer from stdout.
-    pub fn receive_answer(&mut self, word: &Option<String>) -> Result<(),Error>{
-
-        if self.random {
-            self.word_bank.FINAL_RAND(self.seed);
-            self.answer = self.word_bank.FINAL[(self.day - 1) as usize].clone();
-            Ok(())
-        }
-        else {
-            if let Some(_answer) = word {
-                self.answer = _answer.to_lowercase().clone();
-            }
-            else{
-                let mut answer: String = String::new();
-                //this place has a fallback of input nothing.
-                if self.is_tty {
-                    print!("Please specify a {} {}: ",
-                            console::style("five-letter").blue().bold(), 
-                            console::style("answer").red().bold());
-                    loop {
-                        io::stdout().flush().unwrap();
-                        answer = String::new();
-                        io::stdin().read_line(&mut answer).expect("Wrong input");
-                        answer = answer.trim().to_string();
-
-                        if !self.word_bank.FINAL.contains(&answer.to_lowercase()) {
-                            print!("{}, please specify again: ", console::style("Answer not in the word bank FINAL").red());
-                            io::stdout().flush().unwrap();
-                            continue;
-                        }
-                        else {
-                            break;
-                        }
-                    }
-                }
-                else {
-                    io::stdin().read_line(&mut answer).expect("Wrong input");
-                    answer = answer.trim().to_string();
-                }
-                self.answer = answer.to_lowercase().clone();
-            }
-
-            if !self.word_bank.FINAL.contains(&self.answer.to_lowercase()) {
-                Err(Error::NotInWordList)
-            }
-            else {
-                Ok(())
-            }
-        }
+    //A game with no fixed answer. Every guess gets the feedback that keeps the most answers alive,
+    //and the game only gives in when a single answer is left.
+    pub fn adversarial(word_bank: Words, difficult: bool, max_guesses: i32) -> Result<Game, Error> {
+        let mut pool = word_bank.FINAL.clone();
+        pool.sort();
+
+        //any answer will do to begin with, it is swapped as the pool shrinks
+        let first = match pool.first() {
+            Some(w) => w.clone(),
+            None => return Err(Error::NotInWordList),
+        };
+        let mut game = Game::new(&first, word_bank, difficult, max_guesses)?;
+        game.pool = pool;
+        Ok(game)
     }
 
-    //Entry of each game.
-    pub fn game_on(&mut self) -> Result<(Vec<String>, i32), Error>{
+    pub fn is_adversarial(&self) -> bool {
+        !self.pool.is_empty()
+    }
 
-        let mut round = 1;
+    //Submit a guess. Rejected guesses leave the game untouched,
+    //accepted ones are scored and the win/lose bookkeeping is done here.
+    pub fn guess(&mut self, word: &str) -> Result<GuessResult, Error> {
 
-        if self.is_tty{
-            self.print_all();
+        if self.game_state != GameState::Going {
+            return Err(Error::GameOver);
         }
 
-        while round <= 6 {
-
-            if self.is_tty {
-                println!("{} : {} / 6", console::style("Round").green(), console::style(round).blue());
-                print!("Make a guess: ");
-                io::stdout().flush().unwrap();
-            }
-
-            let mut guess: String = String::new();
-            io::stdin().read_line(&mut guess).expect("Wrong input");
-            let guess = guess.trim().to_string();
-
-            match self.check(&guess.to_lowercase()) {
-
-                Ok(()) => {
-                    let _word_display = self.letters_update(&guess);
-
-                    if !self.is_tty {
-                        self.print_state(&_word_display);
-                    }
-                    
-                    if self.is_tty {
-                        self.print_all();
-                        println!("{} {} {}", console::style("CORRECT").yellow(), console::style(self.answer.to_uppercase()).green(), round);
-                    }
-                    else {
-                        println!("CORRECT {}", round);
-                    }
-                    
-                    return Ok((self.guesses.clone(),round));
-                }
-
-                Err(Error::WrongAnswer) => {
-                    let _word_display = self.letters_update(&guess);
-                    if self.is_tty {
-                        self.print_all();
-                    }
-                    else {
-                        self.print_state(&_word_display);
-                    }
-                    round += 1;
-                }
-
-                Err(Error::NotInWordList) => {
-                    if self.is_tty {
-                        println!("{}",console::style("The word is not in word list, try once more.").red());
-                        io::stdout().flush().unwrap();
-                    }
-                    else {
-                        println!("INVALID");
-                    }
-                    continue;
-                }
+        let word = word.to_lowercase();
+        let mut correct = match self.check(&word) {
+            Ok(()) => true,
+            Err(Error::WrongAnswer) => false,
+            Err(e) => return Err(e),
+        };
+
+        if self.is_adversarial() {
+            self.dodge(&word);
+            correct = word == self.answer;
+        }
 
-                Err(Error::DisobeyingDifficult) => {
-                    if self.is_tty {
-                        println!("{}",console::style("Not using the hint, try once more").red());
-                        io::stdout().flush().unwrap();
-                    }
-                    else {
-                        println!("INVALID");
-                    }
-                    continue;
-                }
+        let states = self.letters_update(&word);
+        self.round += 1;
 
-                _ => unimplemented!("Unknown mistakes")
-            }
+        if correct {
+            self.game_state = GameState::Win;
         }
-        
-        if round == 7 {
-            if self.is_tty {
-                print!("{} ", console::style("FAILED").red());
-                let mut pos = 0;
-                for i in self.answer.chars() {
-                    color_print(i, &self.answer_state[pos]);
-                    pos += 1;
-                }
-                io::stdout().flush().unwrap();
-                println!("");
-            }
-            else {
-                println!("FAILED {}", self.answer.to_uppercase());
-            }
+        else if self.guesses.len() as i32 >= self.max_guesses {
+            self.game_state = GameState::Lose;
         }
-        return Err(Error::GameLose(self.guesses.clone()));
-    }
 
-    //Print the word and current states of letters
-    pub fn print_state(&self, word: &[LetterState; 5]) {
-        for i in 0..5{
-            print!("{}", word[i].to_state());
-        }
-        print!(" ");
-        for i in ALPHABET.chars() {
-            print!("{}", self.Letters.get(&i).unwrap().to_state());
-        }
-        println!("");
+        Ok(GuessResult { states, letters: self.Letters.clone(), game_state: self.game_state })
     }
 
     //Check whether the word is not in wordlist, correct or wrong answer.
@@ -315,15 +240,16 @@ impl Game {
             else {
                 if self.difficult {
                     let mut pos = 0;
-                    for i in self.answer_state {
+                    for i in &self.answer_state {
+                        let letter = self.answer.chars().nth(pos).unwrap();
                         if let LetterState::Green = i {
-                            if word.chars().nth(pos).unwrap() != self.answer.chars().nth(pos).unwrap() {
-                                return Err(Error::DisobeyingDifficult);
+                            if word.chars().nth(pos).unwrap() != letter {
+                                return Err(Error::DisobeyingDifficult(Violation::Green { position: pos, letter }));
                             }
                         }
                         else if let LetterState::Yellow = i {
-                            if !word.contains(self.answer.chars().nth(pos).unwrap()) {
-                                return Err(Error::DisobeyingDifficult);
+                            if !word.contains(letter) {
+                                return Err(Error::DisobeyingDifficult(Violation::Yellow { letter }));
                             }
                         }
                         pos += 1;
@@ -335,61 +261,39 @@ impl Game {
     }
 
     //Updating the 26 letters' states
-    pub fn letters_update(&mut self, word: &String) -> [LetterState; 5] {
+    pub fn letters_update(&mut self, word: &String) -> Vec<LetterState> {
 
         let word = word.to_lowercase();
 
-        //Build a counter of each letter in the answer
-        let mut word_hash: HashMap<char, i32> = HashMap::new();
-        //Build a state recorder of the passed-in word
-        let mut _word_display = [LetterState::Unknown; 5];
+        //The feedback of the word, the same rules the solver plays with
+        let _word_display = score(&word, &self.answer);
 
         let mut pos = 0;
-        
-        //count the letter in answer.
-        for i in self.answer.chars() {
-            *word_hash.entry(i).or_insert(0) += 1;
-        }
 
-        //first round to find the green letter
+        //first round to mark the green letter
         for i in word.chars() {
-            if i == self.answer.chars().nth(pos).unwrap() {
+            if let LetterState::Green = _word_display[pos] {
                 //Green should be first noted and no longer changed
-                _word_display[pos] = LetterState::Green;
                 self.Letters.insert(i, LetterState::Green);
                 self.answer_state[pos] = LetterState::Green;
-                *word_hash.entry(i).or_insert(1) -= 1;
             }
             pos += 1;
         }
         
         pos = 0;
         
-        //second round to find the yellow letter
+        //second round to mark where the yellow letter is in the answer
         for i in word.chars() {
-            //no more occurrences, marked red.
-            if *word_hash.entry(i).or_insert(0) == 0 {
-                if let LetterState::Green = _word_display[pos] {}
-                else {
-                    _word_display[pos] = LetterState::Red;
-                }
-            }
-            else {
-                if let LetterState::Green = _word_display[pos]{}
-                else {
-                    _word_display[pos] = LetterState::Yellow;
-                    for j in 0..5 {
-                        if self.answer.chars().nth(j).unwrap() == word.chars().nth(pos).unwrap() {
-                            if let LetterState::Green = self.answer_state[j] {}
-                            else {
-                                self.answer_state[j] = LetterState::Yellow;
-                                break;
-                            }
+            if let LetterState::Yellow = _word_display[pos] {
+                for j in 0..self.length() {
+                    if self.answer.chars().nth(j).unwrap() == i {
+                        if let LetterState::Green = self.answer_state[j] {}
+                        else {
+                            self.answer_state[j] = LetterState::Yellow;
+                            break;
                         }
                     }
-                    *word_hash.entry(i).or_insert(1) -= 1;
                 }
-                
             }
             pos += 1;
         }
@@ -417,54 +321,48 @@ impl Game {
         _word_display
     }
 
-    fn print_keyboard(&self) {
-        for i in KEYBOARD_1.chars() {
-            color_print(i, self.Letters.get(&i).unwrap());
+    //Absurdle: keep the feedback that leaves the most answers in the pool.
+    //On a tie the feedback with less greens, then less yellows is taken, so the game gives in as late as it can.
+    fn dodge(&mut self, word: &str) {
+        let mut groups: HashMap<Vec<LetterState>, Vec<String>> = HashMap::new();
+        for w in &self.pool {
+            groups.entry(score(word, w)).or_default().push(w.clone());
         }
-        io::stdout().flush().unwrap();
-        println!("");
 
-        for i in KEYBOARD_2.chars() {
-            color_print(i, self.Letters.get(&i).unwrap());
-        }
-        io::stdout().flush().unwrap();
-        println!("");
-
-        for i in KEYBOARD_3.chars() {
-            color_print(i, self.Letters.get(&i).unwrap());
-        }
-        io::stdout().flush().unwrap();
-        println!("");
-    }
-
-    fn print_guesses(&self) {
-        let mut x = 0;
-        for i in &self.guesses {
-            let mut y = 0;
-            for j in i.chars() {
-                color_print(j, &self.guesses_state[x][y]);
-                y += 1;
+        let count = |states: &[LetterState], state: LetterState| states.iter().filter(|s| **s == state).count();
+        let (_, kept) = groups.into_iter()
+            .max_by_key(|(states, words)| (words.len(),
+                std::cmp::Reverse(count(states, LetterState::Green)),
+                std::cmp::Reverse(count(states, LetterState::Yellow)),
+                std::cmp::Reverse(states.iter().map(|s| s.to_state()).collect::<String>())))
+            .unwrap();
+        self.pool = kept;
+
+        //every word left gives the same feedback to all the guesses,
+        //so the answer is swapped for one of them and the board is worked out again
+        if !self.pool.contains(&self.answer) {
+            self.answer = self.pool[0].clone();
+
+            let guesses = std::mem::take(&mut self.guesses);
+            self.guesses_state.clear();
+            self.answer_state = vec![LetterState::Unknown; self.answer_state.len()];
+            for i in ALPHABET.chars() {
+                self.Letters.insert(i, LetterState::Unknown);
+            }
+            for g in guesses {
+                self.letters_update(&g);
             }
-            println!("");
-            x += 1;
         }
     }
 
-    fn print_all(&self) {
-        clear_screen();
-
-        println!("{}", console::style("Wordle").bold().bright().green());
-
-        println!("");
-
-        self.print_guesses();
-
-        println!("");
-
-        self.print_keyboard();
-
-        println!("");
+    //Number of letters in the answer
+    pub fn length(&self) -> usize {
+        self.answer_state.len()
     }
 
+    //The word bank this game is played with
+    pub fn word_bank(&self) -> &Words {
+        &self.word_bank
+    }
 
 }
\ No newline at end of file
@@ -0,0 +1,22 @@
+//The library side of the game: a headless engine plus the egui front end.
+//The terminal front end in main.rs is built on top of it.
+
+pub mod game;
+pub use game::*;
+pub mod multi;
+pub mod daily;
+pub mod share;
+
+pub mod words_gen;
+pub mod error;
+
+pub mod builtin_words;
+
+pub mod solver;
+pub mod bench;
+pub mod stats;
+
+pub mod state;
+pub mod config;
+
+pub mod app;
@@ -4,31 +4,18 @@ use console;
 
 use serde_json::Value;
 
-use clap::Parser;
+use clap::{CommandFactory, Parser};
 
-use game::Game;
-
-
-mod game;
-use game::*;
+use wordle::game::*;
+use wordle::words_gen::{self, Words};
+use wordle::multi::{self, MultiGame};
+use wordle::{bench, state, stats, config, daily, error, share};
 
 mod args;
 use args::*;
 
-mod app;
-use app::*;
-
-mod words_gen;
-
-mod builtin_words;
-
-mod state;
-mod config;
-
-fn clear_screen() {
-    print!("\x1B[2J\x1B[1;1H");
-    io::stdout().flush().unwrap();
-}
+mod cli;
+use cli::clear_screen;
 
 //If the the parameter in arg are None, that in config will take over it.
 fn ConfigOverride(arg: &mut Args, config: config::GameConfig) {
@@ -41,6 +28,34 @@ fn ConfigOverride(arg: &mut Args, config: config::GameConfig) {
     if let Some(true) = config.stats {
         arg.stats = true;
     }
+    if let Some(true) = config.streak {
+        arg.streak = true;
+    }
+    if let Some(true) = config.share {
+        arg.share = true;
+    }
+    if let Some(true) = config.high_contrast {
+        arg.high_contrast = true;
+    }
+    if let Some(true) = config.daily {
+        arg.daily = true;
+    }
+    if let None = arg.epoch {
+        if let Some(e) = config.epoch {
+            match e.parse() {
+                Ok(date) => arg.epoch = Some(date),
+                Err(_) => cli::exit_on_error(error::Error::ConfigDamaged {
+                    path: arg.config.clone().unwrap_or_default(),
+                    message: format!("epoch {} is not a date as YYYY-MM-DD", e) }),
+            }
+        }
+    }
+    if let Some(true) = config.adversarial {
+        arg.adversarial = true;
+    }
+    if let Some(true) = config.allow_external_words {
+        arg.allow_external_words = true;
+    }
     if let None = arg.day {
         if let Some(d) = config.day {
             arg.day = Some(d);
@@ -66,11 +81,123 @@ fn ConfigOverride(arg: &mut Args, config: config::GameConfig) {
             arg.state = Some(s);
         }
     }
+    if let None = arg.player {
+        if let Some(p) = config.player {
+            arg.player = Some(p);
+        }
+    }
     if let None = arg.word {
         if let Some(w) = config.word {
             arg.word = Some(w);
         }
     }
+    if let None = arg.length {
+        if let Some(l) = config.length {
+            arg.length = Some(l);
+        }
+    }
+    if let None = arg.max_guesses {
+        if let Some(m) = config.max_guesses {
+            if m < 1 {
+                cli::exit_on_error(error::Error::ConfigDamaged {
+                    path: arg.config.clone().unwrap_or_default(),
+                    message: format!("max_guesses {} is not at least 1", m) });
+            }
+            arg.max_guesses = Some(m);
+        }
+    }
+    if let None = arg.boards {
+        if let Some(b) = config.boards {
+            if !multi::BOARDS.contains(&b) {
+                cli::exit_on_error(error::Error::ConfigDamaged {
+                    path: arg.config.clone().unwrap_or_default(),
+                    message: format!("boards {} is not one of {:?}", b, multi::BOARDS) });
+            }
+            arg.boards = Some(b);
+        }
+    }
+
+    //clap keeps these modes apart on the command line, a config file is held to the same
+    let given = [("word", arg.word.is_some()), ("random", arg.random), ("day", arg.day.is_some()),
+        ("seed", arg.seed.is_some()), ("boards", arg.boards.is_some()), ("adversarial", arg.adversarial)];
+    let modes = [("adversarial", arg.adversarial, vec!["word", "random", "boards"]),
+        ("daily", arg.daily, vec!["word", "day", "seed", "adversarial"])];
+    for (mode, on, conflicts) in modes {
+        if let Some((other, _)) = given.iter().find(|(k, set)| on && *set && conflicts.contains(k)) {
+            cli::exit_on_error(error::Error::ConfigDamaged {
+                path: arg.config.clone().unwrap_or_default(),
+                message: format!("{} cannot be used with {}", mode, other) });
+        }
+    }
+}
+
+//The --state file of a command that works on it, from the args or the config
+fn state_path(args_game: &Args, command: &str) -> String {
+    match &args_game.state {
+        Some(f) => f.clone(),
+        None => Args::command().error(clap::error::ErrorKind::MissingRequiredArgument,
+            format!("{} needs the --state file to work on", command)).exit(),
+    }
+}
+
+//Build the word bank from the word-sets in the args
+fn load_words(args_game: &Args) -> Words {
+    Words::from_files(args_game.length.unwrap_or(words_gen::BUILTIN_LENGTH),
+        args_game.final_set.clone(),
+        args_game.acceptable_set.clone(),
+        args_game.allow_external_words)
+        .unwrap_or_else(|e| cli::exit_on_error(e))
+}
+
+//Build the word bank, pick the answer and start a game as the args say
+fn new_game(args_game: &Args, day_added: u64, is_tty: bool) -> Game {
+
+    //Initializing a word bank
+    let mut words = load_words(args_game);
+
+    //no answer to ask for, the game keeps it open
+    if args_game.adversarial {
+        match Game::adversarial(words, args_game.difficult, args_game.max_guesses.unwrap_or(MAX_GUESS_TIMES)) {
+            Ok(g) => return g,
+            Err(_) => cli::exit_on_error(error::Error::EmptyFinalSet),
+        }
+    }
+
+    let answer = cli::receive_answer(&mut words,
+        args_game.random,
+        args_game.day.unwrap_or(1) + day_added,
+        args_game.seed.unwrap_or(2024),
+        &args_game.word,
+        is_tty);
+
+    //Initializing a game
+    match Game::new(&answer, words, args_game.difficult, args_game.max_guesses.unwrap_or(MAX_GUESS_TIMES)) {
+        Ok(g) => g,
+        Err(_) => cli::exit_on_error(error::Error::AnswerNotInList(answer))
+    }
+}
+
+//The same for a multi-board game
+fn new_multi(args_game: &Args, boards: usize, day_added: u64, is_tty: bool) -> MultiGame {
+
+    let mut words = load_words(args_game);
+
+    let answers = cli::receive_answers(&mut words,
+        args_game.random,
+        args_game.day.unwrap_or(1) + day_added,
+        args_game.seed.unwrap_or(2024),
+        &args_game.word,
+        is_tty,
+        boards);
+
+    let max_guesses = args_game.max_guesses.unwrap_or(multi::default_max_guesses(boards));
+    match MultiGame::new(&answers, words.clone(), args_game.difficult, max_guesses) {
+        Ok(g) => g,
+        Err(_) => {
+            let wrong = answers.iter().find(|a| !words.FINAL.contains(a)).cloned().unwrap_or_default();
+            cli::exit_on_error(error::Error::AnswerNotInList(wrong))
+        }
+    }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -85,17 +212,143 @@ fn main() {
     //Initializing an arg to receive args
     let mut args_game = args::Args::parse();
 
+    //The board of the web build in a native window, eframe keeps its state on disk
+    if args_game.gui {
+        env_logger::init();
+        let options = eframe::NativeOptions {
+            viewport: eframe::egui::ViewportBuilder::default()
+                .with_title("Wordle")
+                .with_inner_size([1280.0, 800.0]),
+            ..Default::default()
+        };
+        if let Err(e) = eframe::run_native("Wordle", options, Box::new(|cc| Ok(Box::new(wordle::app::GuiApp::new(cc))))) {
+            eprintln!("{} {}", console::style("error:").red().bold(), e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
     //Exam on the existence of config file
     //Note: should be taken right after the initialization of args_game
     if let Some(c) = &args_game.config {
 
 
-        let mut _config = config::load_config(c).expect("Config file damaged");
+        let mut _config = config::load_config(c).unwrap_or_else(|e| cli::exit_on_error(e));
         
         ConfigOverride(&mut args_game, _config);
         
     }
 
+    //The daily puzzle is the random answer of the day counted from the epoch, with a fixed seed
+    let daily = if args_game.daily {
+        let date = daily::today();
+        //a multi-board puzzle takes a run of answers a day
+        let answers = load_words(&args_game).FINAL.len() / args_game.boards.unwrap_or(1).max(1);
+        args_game.random = true;
+        args_game.seed = Some(daily::SEED);
+        args_game.day = Some(daily::day_of(date, args_game.epoch.unwrap_or_else(daily::default_epoch), answers));
+        Some(date.to_string())
+    }
+    else {
+        None
+    };
+
+    //the default player is the one keeping the games at the top of the state file
+    let player_name = args_game.player.clone().filter(|p| p != state::DEFAULT_PLAYER);
+    let player = player_name.as_deref();
+
+    if let Some(Command::Solve { strategy, start }) = args_game.command.clone() {
+        let mut game = new_game(&args_game, 0, is_tty);
+        cli::solve(&mut game, strategy, start, is_tty);
+        return;
+    }
+
+    if let Some(Command::Replay { game }) = args_game.command.clone() {
+        let f_name = state_path(&args_game, "replay");
+        let state_game = state::load_state(&f_name).unwrap_or_else(|e| cli::exit_on_error(e));
+        match state_game.games_of(player).get(game as usize - 1) {
+            //a game keeps the word-sets it was played with, the older records take those of the args
+            Some(record) => {
+                let words = match &record.words {
+                    Some(sets) => sets.to_words().unwrap_or_else(|e| cli::exit_on_error(e)),
+                    None => load_words(&args_game),
+                };
+                cli::replay(record, words, is_tty)
+            }
+            None => cli::exit_on_error(error::Error::GameNotInState { path: f_name, game }),
+        }
+        return;
+    }
+
+    //an older state file keeps its version when played on, it is only upgraded here
+    if let Some(Command::Migrate) = args_game.command {
+        let f_name = state_path(&args_game, "migrate");
+        state::update(&f_name, |s| s.version = state::VERSION).unwrap_or_else(|e| cli::exit_on_error(e));
+        return;
+    }
+
+    if let Some(Command::Stats { format }) = args_game.command.clone() {
+        let f_name = state_path(&args_game, "stats");
+        let state_game = state::load_state(&f_name).unwrap_or_else(|e| cli::exit_on_error(e));
+        let report = stats::compute(state_game.games_of(player));
+        match format {
+            Format::Text => cli::print_stats(&report),
+            Format::Json => println!("{}", serde_json::to_string_pretty(&report).unwrap()),
+            Format::Csv => print!("{}", report.to_csv()),
+        }
+        return;
+    }
+
+    if let Some(Command::Leaderboard { format }) = args_game.command.clone() {
+        let f_name = state_path(&args_game, "leaderboard");
+        let state_game = state::load_state(&f_name).unwrap_or_else(|e| cli::exit_on_error(e));
+        let board = stats::leaderboard(&state_game.all_players());
+        match format {
+            Format::Text => cli::print_leaderboard(&board),
+            Format::Json => println!("{}", serde_json::to_string_pretty(&board).unwrap()),
+            Format::Csv => print!("{}", stats::leaderboard_csv(&board)),
+        }
+        return;
+    }
+
+    if let Some(Command::Bench { strategy, start, sample, threads, format }) = args_game.command.clone() {
+        let mut words = load_words(&args_game);
+
+        //every answer in order, or a sample shuffled by the seed
+        let answers: Vec<String> = match sample {
+            Some(n) => {
+                words.FINAL_RAND(args_game.seed.unwrap_or(2024));
+                words.FINAL.iter().take(n).cloned().collect()
+            }
+            None => {
+                let mut all = words.FINAL.clone();
+                all.sort();
+                all
+            }
+        };
+
+        //bench::run takes a start word it can play and at least one answer
+        if answers.is_empty() {
+            cli::exit_on_error(error::Error::EmptyFinalSet);
+        }
+        if let Some(s) = &start {
+            if !words.ACCEPTABLE.contains(&s.to_lowercase()) {
+                cli::exit_on_error(error::Error::GuessNotInList(s.clone()));
+            }
+        }
+
+        let threads = threads.unwrap_or_else(|| std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1));
+        let report = bench::run(&words, &answers, strategy, start, args_game.difficult,
+            args_game.max_guesses.unwrap_or(MAX_GUESS_TIMES), threads);
+
+        match format {
+            Format::Text => cli::print_report(&report),
+            Format::Json => println!("{}", serde_json::to_string_pretty(&report).unwrap()),
+            Format::Csv => print!("{}", report.to_csv()),
+        }
+        return;
+    }
+
     
     //to keep game in loop if Y
     let mut is_game_on = true;
@@ -108,51 +361,40 @@ fn main() {
     let mut win = 0;
     let mut total = 0;
     let mut round = 0;
+    //wins with the help of hints
+    let mut assisted = 0;
+    let mut streak = state::Streak::default();
 
     //If state file exist, update the state_game
     if let Some(f_name) = args_game.state.clone() {
-        if let Ok(mut f) =  std::fs::File::open(&f_name) {
-            let mut data = String::new();
-            std::io::Read::read_to_string(&mut f, &mut data).unwrap();
-
-            if data.trim() == "{}" || data.trim() == "" {
-                //data = "{\n \"total_rounds\": 0,\n \"games\": []\n}".to_string();}
-
-                //A more elegant writing style
-                data = r#" 
-                {
-                 "total_rounds": 0,
-                 "games": []
-                }
-                
-                "#.to_string();
+        state_game = state::load_state(&f_name).unwrap_or_else(|e| cli::exit_on_error(e));
+        streak = state_game.streak_of(player);
+        if state_game.recovered {
+            eprintln!("{} {} is damaged, its backup is used", console::style("warning:").yellow().bold(), f_name);
+        }
+        for _game in state_game.games_of(player) {
+            for word in _game.guesses.clone() {
+                *word_bank.entry(word).or_insert(0) += 1;
             }
-
-            match serde_json::from_str(&data) {
-                Ok(json) =>  {
-                    state_game = json;
-                    for _game in &state_game.games {
-                        for word in _game.guesses.clone() {
-                            *word_bank.entry(word).or_insert(0) += 1;
-                            round += 1;
-                        }
-                        if _game.guesses.contains(&_game.answer) {
-                            win += 1;
-                        }
-                        else {
-                            round -= 6;
-                        }
-                        total += 1;
-                    }
-                }
-                Err(_) => {
-                    panic!("State file damaged!")
+            //only the rounds of the won games count into the average
+            if _game.is_win() {
+                win += 1;
+                round += _game.guesses.len() as i32;
+                if _game.hints > 0 {
+                    assisted += 1;
                 }
             }
+            total += 1;
         }
     }
 
 
+    if let Some(date) = &daily {
+        if state_game.played_daily(player, date) {
+            cli::exit_on_error(error::Error::DailyPlayed(date.clone()));
+        }
+    }
+
     let mut day_added: u64 = 0;
 
     //Entry of game
@@ -170,20 +412,34 @@ fn main() {
             println!("Welcome to {}", console::style("Wordle").bold().bright().green())
         }
             
-            //Initializing a game
-            let mut game = Game::new(args_game.random, 
-                args_game.difficult, 
-                Some(args_game.day.unwrap_or(1) + day_added), 
-                args_game.seed, is_tty,
-                args_game.final_set.clone(),
-                args_game.acceptable_set.clone());
-            
-            match game.receive_answer(&args_game.word) {
-                Ok(_) => {}
-                Err(_) => panic!("Failed to specifying the answer. Not in wordlist.")
-            }
+            //Initializing a game, the record is what goes into the state file
+            let (result, record) = match args_game.boards {
+                Some(boards) if boards > 1 => {
+                    let mut game = new_multi(&args_game, boards, day_added, is_tty);
+                    let result = cli::multi_on(&mut game, is_tty);
+
+                    if args_game.share {
+                        let number = if args_game.random { Some(args_game.day.unwrap_or(1) + day_added) } else { None };
+                        println!("{}", share::share_multi(&game, number, args_game.high_contrast));
+                    }
+                    (result, game.to_state())
+                }
+                _ => {
+                    let mut game = new_game(&args_game, day_added, is_tty);
+                    let result = cli::game_on(&mut game, is_tty);
+
+                    //only a puzzle picked by the day has a number to share
+                    if args_game.share {
+                        let number = if args_game.random { Some(args_game.day.unwrap_or(1) + day_added) } else { None };
+                        println!("{}", share::share_text(&game, number, args_game.high_contrast));
+                    }
+                    (result, state::Game { answer: game.answer.to_uppercase(), guesses: game.guesses, hints: game.hints, max_guesses: Some(game.max_guesses), ..state::Game_new() })
+                }
+            };
 
-            match game.game_on() {
+            let won = result.is_ok();
+            streak.record(won);
+            match result {
 
                 Ok((guesses, _round)) => {
                     for guess in guesses {
@@ -192,6 +448,9 @@ fn main() {
                     win += 1;
                     total += 1;
                     round += _round;
+                    if record.hints > 0 {
+                        assisted += 1;
+                    }
                 }
 
                 Err(Error::GameLose(guesses)) => {
@@ -201,7 +460,13 @@ fn main() {
                     total += 1;
                 }
 
-                _ => unimplemented!("Game loop error!")
+                //a game that was over before it began is counted as lost
+                Err(_) => {
+                    for guess in &record.guesses {
+                        *word_bank.entry(guess.clone()).or_insert(0) += 1;
+                    }
+                    total += 1;
+                }
             }
 
             if args_game.stats {
@@ -221,8 +486,13 @@ fn main() {
                     ratio = round as f64 / win as f64;
                 }
                 
-                //print X Y Z: win lose avg_round_in_win
-                println!("{} {} {:.2}", win, total - win, ratio);
+                //print X Y Z: win lose avg_round_in_win, then the streaks if asked
+                if args_game.streak {
+                    println!("{} {} {:.2} {} {}", win, total - win, ratio, streak.current, streak.best);
+                }
+                else {
+                    println!("{} {} {:.2}", win, total - win, ratio);
+                }
 
                 //print five the most frequently occurring words
                 let mut cnt = 0;
@@ -240,39 +510,72 @@ fn main() {
                 }
                 io::stdout().flush().unwrap();
                 println!("");
+
+                //only in a terminal, the plain output stays as the tests expect it
+                if is_tty && assisted > 0 {
+                    println!("{} {} {} {}", console::style("Assisted wins:").yellow(), assisted,
+                        console::style("Unassisted wins:").green(), win - assisted);
+                }
             }
 
-            if is_tty{
-                print!("Start one more game? [Y/N]: ");
-                io::stdout().flush().unwrap();
+            //there is one daily puzzle a day, so no more game is offered
+            if daily.is_some() {
+                is_game_on = false;
             }
+            else {
+                if is_tty{
+                    print!("Start one more game? [Y/N]: ");
+                    io::stdout().flush().unwrap();
+                }
 
-            let mut is_continue = String::new();
-            io::stdin().read_line(&mut is_continue).expect("Reading Error");
+                let mut is_continue = String::new();
+                io::stdin().read_line(&mut is_continue).expect("Reading Error");
 
-            let is_continue = is_continue.trim().to_string();
+                let is_continue = is_continue.trim().to_string();
             
-            if is_continue == "Y".to_string() 
-                || is_continue == "y".to_string()
-                || is_continue == "Yes".to_string()
-                || is_continue == "yes".to_string(){
-                    is_game_on = true;
+                if is_continue == "Y".to_string() 
+                    || is_continue == "y".to_string()
+                    || is_continue == "Yes".to_string()
+                    || is_continue == "yes".to_string(){
+                        is_game_on = true;
+                    }
+                else if is_continue == "N".to_string() 
+                    || is_continue == "0".to_string() 
+                    || is_continue == "n".to_string()
+                    || is_continue == "No".to_string()
+                    || is_continue == "no".to_string() {
+                    is_game_on = false;
                 }
-            else if is_continue == "N".to_string() 
-                || is_continue == "0".to_string() 
-                || is_continue == "n".to_string()
-                || is_continue == "No".to_string()
-                || is_continue == "no".to_string() {
-                is_game_on = false;
             }
 
             if let Some(f_name) = args_game.state.clone() {
                 
-                state_game.total_rounds += 1;
-                state_game.games.push(state::Game { answer: game.answer.to_uppercase(), guesses: game.guesses});
-                
-                let json = serde_json::to_string_pretty(&state_game).unwrap();
-                std::fs::write(f_name, json).unwrap();
+                //seed and day only tell the answer of a random game, the daily one included, as the GUI keeps them
+                let random = args_game.random && !args_game.adversarial;
+                let record = state::Game {
+                    daily: daily.clone(),
+                    date: Some(chrono::Utc::now()),
+                    seed: if random { Some(args_game.seed.unwrap_or(2024)) } else { None },
+                    day: if random { Some(args_game.day.unwrap_or(1) + day_added) } else { None },
+                    difficult: Some(args_game.difficult),
+                    words: Some(state::WordSets {
+                        final_set: args_game.final_set.clone(),
+                        acceptable_set: args_game.acceptable_set.clone(),
+                        length: args_game.length.unwrap_or(words_gen::BUILTIN_LENGTH),
+                        external: args_game.allow_external_words,
+                    }),
+                    win: Some(won),
+                    ..record
+                };
+
+                //read again under the lock, another terminal may have saved games since
+                match state::update(&f_name, |s| {
+                    s.record(player, record);
+                }) {
+                    //the streak of the file is carried on for the next game
+                    Ok(s) => streak = s.streak_of(player),
+                    Err(e) => cli::exit_on_error(e),
+                }
             }
 
         
@@ -302,7 +605,7 @@ fn main() {
             .start(
                 "the_canvas_id",
                 web_options,
-                Box::new(|cc| Ok(Box::new(app::GuiApp::new(cc)))),
+                Box::new(|cc| Ok(Box::new(wordle::app::GuiApp::new(cc)))),
             )
             .await;
 
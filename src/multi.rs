@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game::{Error, Game, GameState, GuessResult};
+use crate::state;
+use crate::words_gen::Words;
+
+//The number of boards a multi-board game can be played with
+pub const BOARDS: [usize; 3] = [2, 4, 8];
+
+//Several boards played with one stream of guesses, each board with its own answer.
+//A board stops taking guesses once it is solved, the game is won when every board is.
+#[derive(Deserialize, Serialize)]
+pub struct MultiGame {
+    pub boards: Vec<Game>,
+    pub guesses: Vec<String>,
+    pub round: i32,
+    pub max_guesses: i32,
+    pub game_state: GameState,
+}
+
+//Guesses allowed by default: one more for every board beyond the first
+pub fn default_max_guesses(boards: usize) -> i32 {
+    crate::game::MAX_GUESS_TIMES + boards as i32 - 1
+}
+
+impl MultiGame {
+
+    //Initializing the boards, every answer must be in the FINAL of the bank
+    pub fn new(answers: &[String], word_bank: Words, difficult: bool, max_guesses: i32) -> Result<MultiGame, Error> {
+        let mut boards = Vec::new();
+        for answer in answers {
+            boards.push(Game::new(answer, word_bank.clone(), difficult, max_guesses)?);
+        }
+
+        Ok(MultiGame {boards,
+            guesses: Vec::new(),
+            round: 1,
+            max_guesses,
+            game_state: GameState::Going})
+    }
+
+    //Submit a guess to every board not solved yet.
+    //The guess is rejected as a whole if any of those boards rejects it,
+    //otherwise each board gives its feedback, None for the boards already solved.
+    pub fn guess(&mut self, word: &str) -> Result<Vec<Option<GuessResult>>, Error> {
+
+        if self.game_state != GameState::Going {
+            return Err(Error::GameOver);
+        }
+
+        let word = word.to_lowercase();
+        for board in self.boards.iter().filter(|b| b.game_state == GameState::Going) {
+            match board.check(&word) {
+                Ok(()) | Err(Error::WrongAnswer) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        let mut results = Vec::new();
+        for board in &mut self.boards {
+            if board.game_state == GameState::Going {
+                results.push(Some(board.guess(&word)?));
+            }
+            else {
+                results.push(None);
+            }
+        }
+
+        self.guesses.push(word.to_uppercase());
+        self.round += 1;
+
+        if self.boards.iter().all(|b| b.game_state == GameState::Win) {
+            self.game_state = GameState::Win;
+        }
+        else if self.guesses.len() as i32 >= self.max_guesses {
+            self.game_state = GameState::Lose;
+        }
+
+        Ok(results)
+    }
+
+    //Number of letters in the answers
+    pub fn length(&self) -> usize {
+        self.boards[0].length()
+    }
+
+    pub fn answers(&self) -> Vec<String> {
+        self.boards.iter().map(|b| b.answer.clone()).collect()
+    }
+
+    //The record kept in the state file
+    pub fn to_state(&self) -> state::Game {
+        let answers: Vec<String> = self.answers().iter().map(|a| a.to_uppercase()).collect();
+        state::Game {
+            answer: answers[0].clone(),
+            guesses: self.guesses.clone(),
+            hints: self.boards.iter().map(|b| b.hints).sum(),
+            answers,
+            max_guesses: Some(self.max_guesses),
+            ..state::Game_new()
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use crate::game::{Game, GameState, LetterState};
+use crate::multi::MultiGame;
+
+//The spoiler-free result of a finished game: a title line, then one row of squares for each guess.
+//The number is the puzzle's day if it has one, and a * marks difficult mode.
+pub fn share_text(game: &Game, number: Option<u64>, high_contrast: bool) -> String {
+    let mut text = title(game.game_state, game.guesses.len(), game.max_guesses, game.difficult, number);
+    for row in &game.guesses_state {
+        text.push('\n');
+        for state in row {
+            text.push(square(state, high_contrast));
+        }
+    }
+    text
+}
+
+//The same for several boards, their rows side by side.
+//A board already solved leaves its place in the later rows blank.
+pub fn share_multi(game: &MultiGame, number: Option<u64>, high_contrast: bool) -> String {
+    let difficult = game.boards.iter().any(|b| b.difficult);
+    let mut text = title(game.game_state, game.guesses.len(), game.max_guesses, difficult, number);
+
+    for i in 0..game.guesses.len() {
+        let row: Vec<String> = game.boards.iter().map(|b| match b.guesses_state.get(i) {
+            Some(states) => states.iter().map(|s| square(s, high_contrast)).collect(),
+            //a square is two columns wide
+            None => "  ".repeat(b.length()),
+        }).collect();
+        text.push('\n');
+        text.push_str(row.join(" ").trim_end());
+    }
+    text
+}
+
+fn title(state: GameState, guesses: usize, max_guesses: i32, difficult: bool, number: Option<u64>) -> String {
+    let score = match state {
+        GameState::Win => guesses.to_string(),
+        _ => "X".to_string(),
+    };
+
+    let mut text = String::from("Wordle");
+    if let Some(n) = number {
+        text.push_str(&format!(" {}", n));
+    }
+    text.push_str(&format!(" {}/{}", score, max_guesses));
+    if difficult {
+        text.push('*');
+    }
+    text
+}
+
+//High contrast swaps green and yellow for orange and blue
+fn square(state: &LetterState, high_contrast: bool) -> char {
+    match (state, high_contrast) {
+        (LetterState::Green, false) => '🟩',
+        (LetterState::Yellow, false) => '🟨',
+        (LetterState::Green, true) => '🟧',
+        (LetterState::Yellow, true) => '🟦',
+        _ => '⬛',
+    }
+}
@@ -0,0 +1,126 @@
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::game::{self, Game, LetterState};
+use crate::words_gen::Words;
+
+//How the solver ranks a guess
+#[derive(Copy, Clone, Debug, ValueEnum, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Strategy {
+    ///Expected information of the feedback, in bits
+    Entropy,
+    ///Size of the largest group of candidates a feedback can leave
+    Worst,
+}
+
+//Exam if the word could still be the answer after the guesses made so far
+pub fn is_consistent(word: &str, guesses: &[String], guesses_state: &[Vec<LetterState>]) -> bool {
+    guesses.iter().zip(guesses_state)
+        .all(|(g, s)| game::score(&g.to_lowercase(), word) == *s)
+}
+
+//The words of FINAL that agree with every row of the game
+pub fn candidates(game: &Game) -> Vec<String> {
+    game.word_bank().FINAL.iter()
+        .filter(|w| is_consistent(w, &game.guesses, &game.guesses_state))
+        .cloned()
+        .collect()
+}
+
+//Feedback packed into a number, so the groups can be counted in an array
+fn pattern_code(states: &[LetterState]) -> usize {
+    states.iter().fold(0, |code, s| code * 3 + match s {
+        LetterState::Green => 2,
+        LetterState::Yellow => 1,
+        _ => 0,
+    })
+}
+
+pub struct Solver {
+    pub candidates: Vec<String>,
+    pub strategy: Strategy,
+    //the candidates split into letters once, for scoring them over and over
+    letters: Vec<Vec<char>>,
+}
+
+impl Solver {
+
+    //Every word of FINAL is a candidate at the beginning
+    pub fn new(word_bank: &Words, strategy: Strategy) -> Solver {
+        Solver::with_candidates(word_bank.FINAL.clone(), strategy)
+    }
+
+    //Pick up from a game that already has some guesses
+    pub fn from_game(game: &Game, strategy: Strategy) -> Solver {
+        Solver::with_candidates(candidates(game), strategy)
+    }
+
+    fn with_candidates(mut candidates: Vec<String>, strategy: Strategy) -> Solver {
+        candidates.sort();
+        let letters = candidates.iter().map(|w| w.chars().collect()).collect();
+        Solver { candidates, strategy, letters }
+    }
+
+    //Drop the candidates that would not give this feedback
+    pub fn update(&mut self, guess: &str, states: &[LetterState]) {
+        let guess = guess.to_lowercase();
+        let kept: Vec<String> = self.candidates.iter()
+            .filter(|w| game::score(&guess, w) == states)
+            .cloned()
+            .collect();
+        *self = Solver::with_candidates(kept, self.strategy);
+    }
+
+    //Rank a guess against the candidates, the higher the better
+    pub fn rate(&self, guess: &str) -> f64 {
+        let guess: Vec<char> = guess.chars().collect();
+        let mut states = vec![LetterState::Red; guess.len()];
+        let mut groups = vec![0usize; 3usize.pow(guess.len() as u32)];
+        for w in &self.letters {
+            game::score_into(&guess, w, &mut states);
+            groups[pattern_code(&states)] += 1;
+        }
+
+        match self.strategy {
+            Strategy::Entropy => {
+                let total = self.candidates.len() as f64;
+                groups.iter().filter(|&&n| n > 0).map(|&n| {
+                    let p = n as f64 / total;
+                    -p * p.log2()
+                }).sum()
+            }
+            Strategy::Worst => -(*groups.iter().max().unwrap_or(&0) as f64),
+        }
+    }
+
+    //The best next guess for the game, taken from ACCEPTABLE.
+    //In difficult mode only the guesses Game::check allows are played.
+    pub fn best_guess(&self, game: &Game) -> Option<String> {
+        if self.candidates.len() <= 2 {
+            return self.candidates.first().cloned();
+        }
+
+        let mut best: Option<(f64, bool, &String)> = None;
+        for w in &game.word_bank().ACCEPTABLE {
+            if game.difficult {
+                if let Err(game::Error::DisobeyingDifficult(_)) = game.check(w) {
+                    continue;
+                }
+            }
+
+            let rate = self.rate(w);
+            let is_candidate = self.candidates.binary_search(w).is_ok();
+
+            //a guess that may be the answer wins a tie
+            let better = match best {
+                None => true,
+                Some((r, c, _)) => rate > r + 1e-9 || ((rate - r).abs() <= 1e-9 && is_candidate && !c),
+            };
+            if better {
+                best = Some((rate, is_candidate, w));
+            }
+        }
+        best.map(|(_, _, w)| w.clone())
+    }
+}
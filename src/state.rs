@@ -1,22 +1,378 @@
 use serde_json::{Result, Value};
 use serde::{Serialize, Deserialize};
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+
+use crate::error::Error;
+use crate::words_gen::Words;
+use crate::game::MAX_GUESS_TIMES;
+use crate::multi;
+
+//Version of the state file this build writes.
+//1 is the original {total_rounds, games: [{answer, guesses}]}, it has no version key.
+//2 adds what is needed to tell the games apart: when, how and with which words they were played.
+pub const VERSION: u32 = 2;
+
+//The name the games at the top of the file go by, those played without a player
+pub const DEFAULT_PLAYER: &str = "default";
 
 #[derive(Serialize, Deserialize)]
 pub struct GameMaster {
+    #[serde(default = "legacy_version", skip_serializing_if = "is_legacy")]
+    pub version: u32,
     pub total_rounds: i32,
     pub games: Vec<Game>,
+    //kept along with the games, it is worked out from them for a file written without it
+    #[serde(default, skip_serializing_if = "Streak::is_empty")]
+    pub streak: Streak,
+    //the games of every other player, by name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub players: BTreeMap<String, Profile>,
+    //the file was damaged and this is its backup
+    #[serde(skip)]
+    pub recovered: bool,
 }
 
-#[derive(Serialize, Deserialize)]
+//Wins in a row up to the last game, and the longest run of them
+#[derive(Clone, Copy, Default, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Streak {
+    pub current: usize,
+    pub best: usize,
+}
+
+impl Streak {
+
+    pub fn from_games(games: &[Game]) -> Streak {
+        let mut streak = Streak::default();
+        for g in games {
+            streak.record(g.is_win());
+        }
+        streak
+    }
+
+    //Count one more game into the streak
+    pub fn record(&mut self, win: bool) {
+        if win {
+            self.current += 1;
+            self.best = self.best.max(self.current);
+        }
+        else {
+            self.current = 0;
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        *self == Streak::default()
+    }
+}
+
+//The games of a player sharing the file, kept the same way as those at the top of it
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Profile {
+    pub total_rounds: i32,
+    pub games: Vec<Game>,
+    #[serde(default, skip_serializing_if = "Streak::is_empty")]
+    pub streak: Streak,
+}
+
+//The word-sets of a game, None for the builtin ones
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct WordSets {
+    pub final_set: Option<String>,
+    pub acceptable_set: Option<String>,
+    pub length: usize,
+    //played with --allow-external-words
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub external: bool,
+}
+
+impl WordSets {
+
+    //The word bank the game was played with, read from its files again
+    pub fn to_words(&self) -> std::result::Result<Words, Error> {
+        Words::from_files(self.length, self.final_set.clone(), self.acceptable_set.clone(), self.external)
+    }
+}
+
+//One game, as both the CLI and the GUI record it.
+//Everything after guesses is optional, so the version 1 files read as they are.
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Game {
     pub answer: String,
     pub guesses: Vec<String>,
+    //hints asked for during the game, left out of the file when none was used
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub hints: i32,
+    //every answer of a multi-board game, the answer above is then the first board's
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub answers: Vec<String>,
+    //the date of a daily puzzle, which can only be played once
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<DateTime<Utc>>,
+    //seed and day of a random game
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub day: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub difficult: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_guesses: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub words: Option<WordSets>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub win: Option<bool>,
+}
+
+impl Game {
+
+    //A multi-board game is won only when every answer was guessed
+    pub fn is_win(&self) -> bool {
+        if let Some(win) = self.win {
+            return win;
+        }
+        if self.answers.is_empty() {
+            self.guesses.contains(&self.answer)
+        }
+        else {
+            self.answers.iter().all(|a| self.guesses.contains(a))
+        }
+    }
+
+    //The guesses the game allowed. An older record has the default for its number of boards,
+    //and never fewer than it took.
+    pub fn allowed_guesses(&self) -> i32 {
+        let default = if self.answers.is_empty() { MAX_GUESS_TIMES } else { multi::default_max_guesses(self.answers.len()) };
+        self.max_guesses.unwrap_or(default).max(self.guesses.len() as i32)
+    }
+
+    //Only what a version 1 file holds: answer and guesses, and the hints, answers and daily
+    //the builds before version 2 already wrote. Date, seed, day, difficult, max_guesses, words and win are left out.
+    fn to_legacy(&self) -> Game {
+        Game {
+            answer: self.answer.clone(),
+            guesses: self.guesses.clone(),
+            hints: self.hints,
+            answers: self.answers.clone(),
+            daily: self.daily.clone(),
+            ..Game_new()
+        }
+    }
+}
+
+fn is_zero(n: &i32) -> bool {
+    *n == 0
+}
+
+fn legacy_version() -> u32 {
+    1
+}
+
+fn is_legacy(v: &u32) -> bool {
+    *v == 1
+}
+
+impl GameMaster {
+
+    //The games of a player, None for the ones at the top of the file
+    pub fn games_of(&self, player: Option<&str>) -> &[Game] {
+        match player {
+            Some(p) => self.players.get(p).map(|p| p.games.as_slice()).unwrap_or(&[]),
+            None => &self.games,
+        }
+    }
+
+    pub fn streak_of(&self, player: Option<&str>) -> Streak {
+        match player {
+            Some(p) => self.players.get(p).map(|p| p.streak).unwrap_or_default(),
+            None => self.streak,
+        }
+    }
+
+    //Every player with games, the default one included
+    pub fn all_players(&self) -> Vec<(&str, &[Game])> {
+        let mut all = Vec::new();
+        if !self.games.is_empty() {
+            all.push((DEFAULT_PLAYER, self.games.as_slice()));
+        }
+        for (name, p) in &self.players {
+            all.push((name.as_str(), p.games.as_slice()));
+        }
+        all
+    }
+
+    //Count a finished game into the record of its player.
+    //A version 1 file has no room for the players, it is upgraded by the first one saving into it.
+    pub fn record(&mut self, player: Option<&str>, game: Game) {
+        let win = game.is_win();
+        match player {
+            Some(p) => {
+                if self.version < VERSION {
+                    self.version = VERSION;
+                }
+                let profile = self.players.entry(p.to_string()).or_default();
+                profile.total_rounds += 1;
+                profile.streak.record(win);
+                profile.games.push(game);
+            }
+            None => {
+                self.total_rounds += 1;
+                self.streak.record(win);
+                self.games.push(game);
+            }
+        }
+    }
+
+    //Whether the daily puzzle of the date is in the record of the player already
+    pub fn played_daily(&self, player: Option<&str>, date: &str) -> bool {
+        self.games_of(player).iter().any(|g| g.daily.as_deref() == Some(date))
+    }
+
+    //Bring the games of an older file up to this version in memory.
+    //The file itself keeps its version until it is migrated on purpose.
+    fn migrate(&mut self) {
+        if self.version < 2 {
+            for g in &mut self.games {
+                g.win = Some(g.is_win());
+            }
+        }
+        if self.streak.is_empty() {
+            self.streak = Streak::from_games(&self.games);
+        }
+    }
+
+    //The file as it is written, a version 1 file drops the fields it has no place for
+    pub fn to_json(&self) -> String {
+        if self.version >= VERSION {
+            return serde_json::to_string_pretty(self).unwrap();
+        }
+        let legacy = GameMaster {
+            version: self.version,
+            total_rounds: self.total_rounds,
+            games: self.games.iter().map(|g| g.to_legacy()).collect(),
+            streak: Streak::default(),
+            players: BTreeMap::new(),
+            recovered: false,
+        };
+        serde_json::to_string_pretty(&legacy).unwrap()
+    }
 }
 
 pub fn GameMaster_new() -> GameMaster {
-    GameMaster {total_rounds: 0, games: Vec::new()}
+    GameMaster {version: VERSION, total_rounds: 0, games: Vec::new(), streak: Streak::default(), players: BTreeMap::new(), recovered: false}
 }
 
 pub fn Game_new() -> Game {
-    Game {answer: String::new(), guesses: Vec::new()}
-}
\ No newline at end of file
+    Game {answer: String::new(),
+        guesses: Vec::new(),
+        hints: 0,
+        answers: Vec::new(),
+        daily: None,
+        date: None,
+        seed: None,
+        day: None,
+        difficult: None,
+        max_guesses: None,
+        words: None,
+        win: None}
+}
+
+//Next to the state file: the last good copy of it, and the file locked while it is being changed
+fn backup_path(path: &str) -> String {
+    format!("{}.bak", path)
+}
+
+fn lock_path(path: &str) -> String {
+    format!("{}.lock", path)
+}
+
+fn unwritable(path: &str, e: std::io::Error) -> Error {
+    Error::StateUnwritable { path: path.to_string(), message: e.to_string() }
+}
+
+fn parse(path: &str, data: &str) -> std::result::Result<GameMaster, Error> {
+    if data.trim() == "{}" || data.trim() == "" {
+        return Ok(GameMaster {version: 1, total_rounds: 0, games: Vec::new(), streak: Streak::default(), players: BTreeMap::new(), recovered: false});
+    }
+
+    let mut state: GameMaster = serde_json::from_str(data).map_err(|e| Error::StateDamaged {
+        path: path.to_string(),
+        line: e.line(),
+        //serde_json puts the position at the end of its message, the line is kept on its own
+        message: e.to_string().split(" at line ").next().unwrap_or("").to_string(),
+    })?;
+
+    if state.version > VERSION {
+        return Err(Error::StateDamaged {
+            path: path.to_string(),
+            line: 1,
+            message: format!("version {} is newer than this build reads", state.version),
+        });
+    }
+    state.migrate();
+    Ok(state)
+}
+
+//Read the state file. A missing file is a fresh start in the current version,
+//an empty one or {} is what the older builds start from and stays a version 1 file.
+//A damaged file is read from its backup if there is a good one, the error is kept otherwise.
+pub fn load_state(path: &str) -> std::result::Result<GameMaster, Error> {
+    //a file that cannot be read is as damaged as one that cannot be parsed
+    let state = match fs::read_to_string(path) {
+        Ok(data) => parse(path, &data),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(GameMaster_new()),
+        Err(e) => Err(Error::StateDamaged { path: path.to_string(), line: 0, message: e.to_string() }),
+    };
+
+    match state {
+        Ok(state) => Ok(state),
+        Err(e) => {
+            let backup = backup_path(path);
+            match fs::read_to_string(&backup).ok().and_then(|d| parse(&backup, &d).ok()) {
+                Some(mut state) => {
+                    state.recovered = true;
+                    Ok(state)
+                }
+                None => Err(e),
+            }
+        }
+    }
+}
+
+//Write a file whole or not at all: into a temporary one first, then renamed over it
+fn write_atomic(path: &str, data: &str) -> std::io::Result<()> {
+    let tmp = format!("{}.tmp", path);
+    let mut file = File::create(&tmp)?;
+    file.write_all(data.as_bytes())?;
+    file.sync_all()?;
+    fs::rename(&tmp, path)
+}
+
+//Write the state file in its own version.
+//What was there is kept as the backup, unless it is damaged and the backup is better.
+pub fn save_state(path: &str, state: &GameMaster) -> std::result::Result<(), Error> {
+    if let Ok(data) = fs::read_to_string(path) {
+        if parse(path, &data).is_ok() {
+            write_atomic(&backup_path(path), &data).map_err(|e| unwritable(path, e))?;
+        }
+    }
+    write_atomic(path, &state.to_json()).map_err(|e| unwritable(path, e))
+}
+
+//Change the state file with it locked from reading to writing,
+//so the games another terminal saves meanwhile are not lost.
+//The lock is advisory, it only keeps out the others going through here.
+pub fn update<F: FnOnce(&mut GameMaster)>(path: &str, change: F) -> std::result::Result<GameMaster, Error> {
+    let lock = OpenOptions::new().create(true).truncate(false).write(true).open(lock_path(path))
+        .map_err(|e| unwritable(path, e))?;
+    lock.lock().map_err(|e| unwritable(path, e))?;
+
+    let mut state = load_state(path)?;
+    change(&mut state);
+    save_state(path, &state)?;
+    Ok(state)
+}
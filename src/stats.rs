@@ -0,0 +1,207 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::game::MAX_GUESS_TIMES;
+use crate::state::{Game, Streak};
+
+//Statistics of the games in a state file, oldest game first
+#[derive(Serialize)]
+pub struct Stats {
+    pub games: usize,
+    pub wins: usize,
+    pub win_percentage: f64,
+    //wins in a row up to the last game, and the longest run of them
+    pub current_streak: usize,
+    pub max_streak: usize,
+    //average guesses of the won games
+    pub average: f64,
+    //histogram[i] is the number of wins in i + 1 guesses
+    pub histogram: Vec<usize>,
+    pub starts: Vec<WordScore>,
+    pub letters: Vec<LetterCount>,
+    //the hardest answers first
+    pub answers: Vec<WordScore>,
+}
+
+//How the games with a word went, as a start or as an answer.
+//The average counts a lost game as one guess more than allowed.
+#[derive(Serialize)]
+pub struct WordScore {
+    pub word: String,
+    pub games: usize,
+    pub wins: usize,
+    pub average: f64,
+}
+
+#[derive(Serialize)]
+pub struct LetterCount {
+    pub letter: char,
+    pub count: usize,
+}
+
+//A line of the leaderboard
+#[derive(Serialize)]
+pub struct Standing {
+    pub player: String,
+    pub games: usize,
+    pub wins: usize,
+    pub win_percentage: f64,
+    pub average: f64,
+    pub current_streak: usize,
+    pub max_streak: usize,
+}
+
+//games and guesses of a word, summed up before the average is taken
+#[derive(Default)]
+struct Tally {
+    games: usize,
+    wins: usize,
+    guesses: usize,
+}
+
+impl Tally {
+    fn add(&mut self, win: bool, guesses: usize) {
+        self.games += 1;
+        self.guesses += guesses;
+        if win {
+            self.wins += 1;
+        }
+    }
+}
+
+fn scores(tallies: HashMap<String, Tally>) -> Vec<WordScore> {
+    tallies.into_iter().map(|(word, t)| WordScore {
+        word,
+        games: t.games,
+        wins: t.wins,
+        average: t.guesses as f64 / t.games as f64,
+    }).collect()
+}
+
+pub fn compute(games: &[Game]) -> Stats {
+    let longest = games.iter().map(|g| g.max_guesses.unwrap_or(MAX_GUESS_TIMES) as usize)
+        .chain(games.iter().filter(|g| g.is_win()).map(|g| g.guesses.len()))
+        .max()
+        .unwrap_or(MAX_GUESS_TIMES as usize);
+
+    let mut histogram = vec![0; longest];
+    let mut wins = 0;
+    let mut rounds = 0;
+    let mut starts: HashMap<String, Tally> = HashMap::new();
+    let mut answers: HashMap<String, Tally> = HashMap::new();
+    let mut letters: HashMap<char, usize> = HashMap::new();
+
+    for g in games {
+        let win = g.is_win();
+        let score = if win { g.guesses.len() } else { g.max_guesses.unwrap_or(MAX_GUESS_TIMES) as usize + 1 };
+
+        if win {
+            wins += 1;
+            rounds += g.guesses.len();
+            if !g.guesses.is_empty() {
+                histogram[g.guesses.len() - 1] += 1;
+            }
+        }
+
+        if let Some(first) = g.guesses.first() {
+            starts.entry(first.to_uppercase()).or_default().add(win, score);
+        }
+        //every board of a multi-board game is an answer of its own
+        if g.answers.is_empty() {
+            answers.entry(g.answer.to_uppercase()).or_default().add(win, score);
+        }
+        else {
+            for a in &g.answers {
+                answers.entry(a.to_uppercase()).or_default().add(win, score);
+            }
+        }
+        for c in g.guesses.iter().flat_map(|w| w.chars()) {
+            *letters.entry(c.to_ascii_uppercase()).or_insert(0) += 1;
+        }
+    }
+
+    let mut starts = scores(starts);
+    starts.sort_by(|a, b| a.average.total_cmp(&b.average).then(a.word.cmp(&b.word)));
+    let mut answers = scores(answers);
+    answers.sort_by(|a, b| b.average.total_cmp(&a.average).then(a.word.cmp(&b.word)));
+    let mut letters: Vec<LetterCount> = letters.into_iter().map(|(letter, count)| LetterCount { letter, count }).collect();
+    letters.sort_by(|a, b| b.count.cmp(&a.count).then(a.letter.cmp(&b.letter)));
+
+    let streak = Streak::from_games(games);
+
+    Stats {
+        games: games.len(),
+        wins,
+        win_percentage: if games.is_empty() { 0.0 } else { wins as f64 * 100.0 / games.len() as f64 },
+        current_streak: streak.current,
+        max_streak: streak.best,
+        average: if wins == 0 { 0.0 } else { rounds as f64 / wins as f64 },
+        histogram,
+        starts,
+        letters,
+        answers,
+    }
+}
+
+//The players ranked by win rate, then the fewer guesses, then the longer current streak.
+//A player without a win has no average and comes after those with one.
+pub fn leaderboard(players: &[(&str, &[Game])]) -> Vec<Standing> {
+    let mut board: Vec<Standing> = players.iter().map(|(player, games)| {
+        let s = compute(games);
+        Standing {
+            player: player.to_string(),
+            games: s.games,
+            wins: s.wins,
+            win_percentage: s.win_percentage,
+            average: s.average,
+            current_streak: s.current_streak,
+            max_streak: s.max_streak,
+        }
+    }).collect();
+
+    let average = |s: &Standing| if s.wins == 0 { f64::MAX } else { s.average };
+    board.sort_by(|a, b| b.win_percentage.total_cmp(&a.win_percentage)
+        .then(average(a).total_cmp(&average(b)))
+        .then(b.current_streak.cmp(&a.current_streak))
+        .then(a.player.cmp(&b.player)));
+    board
+}
+
+//The leaderboard with a line for each player
+pub fn leaderboard_csv(board: &[Standing]) -> String {
+    let mut csv = String::from("rank,player,games,wins,win_percentage,average,current_streak,max_streak\n");
+    for (i, s) in board.iter().enumerate() {
+        csv.push_str(&format!("{},{},{},{},{:.2},{:.3},{},{}\n", i + 1, s.player, s.games, s.wins,
+            s.win_percentage, s.average, s.current_streak, s.max_streak));
+    }
+    csv
+}
+
+impl Stats {
+
+    //One value on each line, the section telling what it is
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("section,key,value\n");
+        csv.push_str(&format!("summary,games,{}\n", self.games));
+        csv.push_str(&format!("summary,wins,{}\n", self.wins));
+        csv.push_str(&format!("summary,win_percentage,{:.2}\n", self.win_percentage));
+        csv.push_str(&format!("summary,current_streak,{}\n", self.current_streak));
+        csv.push_str(&format!("summary,max_streak,{}\n", self.max_streak));
+        csv.push_str(&format!("summary,average,{:.3}\n", self.average));
+        for (i, n) in self.histogram.iter().enumerate() {
+            csv.push_str(&format!("histogram,{},{}\n", i + 1, n));
+        }
+        csv.push_str(&format!("histogram,X,{}\n", self.games - self.wins));
+        for s in &self.starts {
+            csv.push_str(&format!("start,{},{:.3}\n", s.word, s.average));
+        }
+        for l in &self.letters {
+            csv.push_str(&format!("letter,{},{}\n", l.letter, l.count));
+        }
+        for a in &self.answers {
+            csv.push_str(&format!("answer,{},{:.3}\n", a.word, a.average));
+        }
+        csv
+    }
+}
@@ -8,29 +8,71 @@ use rand::rngs::StdRng;
 use rand::SeedableRng;
 use serde::{Deserialize, Serialize};
 
-pub enum Error {
-    FileNotExist,
-    DuplicatedWords,
-    BeyondBuiltinWords
-}
+//The builtin word lists only hold five-letter words,
+//other lengths have to come with their own final-set and acceptable-set.
+pub const BUILTIN_LENGTH: usize = 5;
+pub const MIN_LENGTH: usize = 4;
+pub const MAX_LENGTH: usize = 8;
+
+pub use crate::error::Error;
 
 //This builds a bank to store the real FINAL and ACCEPTABLE words in a game
-#[derive(Deserialize, Serialize)]
+#[derive(Clone, Deserialize, Serialize)]
 pub struct Words {
     pub FINAL: Vec<String>,
-    pub ACCEPTABLE: Vec<String>
+    pub ACCEPTABLE: Vec<String>,
+    #[serde(default = "builtin_length")]
+    pub length: usize,
+    //words beyond the builtin lists are let in
+    #[serde(default)]
+    pub external: bool
+}
+
+fn builtin_length() -> usize {
+    BUILTIN_LENGTH
 }
 
-//Inspired by ChatGPT
-//Exam if there are duplicated words in a word-set
-fn to_hashset(_words: &Vec<String>) -> Result<HashSet<String>, Error>{
-    let mut hashset: HashSet<String> = HashSet::new();
-    for i in _words {
-        if !hashset.insert(i.to_string()) {
-            return Err(Error::DuplicatedWords);
+//Read a word-set file, one word a line.
+//Words of the wrong length or with letters out of the alphabet are reported first,
+//then the duplicated ones, then the words beyond the builtin list if one is given.
+fn read_set(path: &str, length: usize, builtin: Option<&[&str]>) -> Result<Vec<String>, Error> {
+    let _content = match std::fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Err(Error::FileNotExist { path: path.to_string() }),
+        Err(e) => return Err(Error::FileUnreadable { path: path.to_string(), message: e.to_string() }),
+    };
+
+    //(line number, word)
+    let _words: Vec<(usize, String)> = _content.lines().enumerate()
+        .map(|(i, s)| (i + 1, s.to_string().to_lowercase()))
+        .collect();
+
+    if let Some((line, word)) = _words.iter().find(|(_, w)| w.chars().count() != length) {
+        return Err(Error::WrongLength { path: path.to_string(), line: *line, word: word.clone() });
+    }
+
+    if let Some((line, word)) = _words.iter().find(|(_, w)| !w.chars().all(|c| c.is_ascii_lowercase())) {
+        return Err(Error::WrongAlphabet { path: path.to_string(), line: *line, word: word.clone() });
+    }
+
+    //Inspired by ChatGPT
+    let mut hashset: HashSet<&String> = HashSet::new();
+    for (line, word) in &_words {
+        if !hashset.insert(word) {
+            return Err(Error::DuplicatedWords { path: path.to_string(), line: *line, word: word.clone() });
+        }
+    }
+
+    if let Some(builtin) = builtin {
+        let ori_set: HashSet<&str> = builtin.iter().copied().collect();
+        if let Some((line, word)) = _words.iter().find(|(_, w)| !ori_set.contains(w.as_str())) {
+            return Err(Error::BeyondBuiltinWords { path: path.to_string(), line: *line, word: word.clone() });
         }
     }
-    Ok(hashset)
+
+    let mut _words: Vec<String> = _words.into_iter().map(|(_, w)| w).collect();
+    _words.sort();
+    Ok(_words)
 }
 
 impl Words {
@@ -39,86 +81,102 @@ impl Words {
     pub fn new() -> Words{
         let _final = FINAL.iter().map(|s| s.to_string()).collect();
         let _acceptable = ACCEPTABLE.iter().map(|s| s.to_string()).collect();
-        Words{FINAL: _final, ACCEPTABLE: _acceptable}
+        Words{FINAL: _final, ACCEPTABLE: _acceptable, length: BUILTIN_LENGTH, external: false}
     }
 
-    pub fn FINAL_set(&mut self, _final_set: String) -> Result<(),Error>{
-        //read from file provided
-        if let Ok(mut f) = std::fs::File::open(_final_set) {
-
-            let mut _content = String::new();
-            std::io::Read::read_to_string(&mut f, &mut _content).unwrap();
-
-            let mut _content: Vec<String> = _content.lines().map(|s| s.to_string().to_lowercase()).collect();
-
-            //Exam if there are words beyond the builtin_words
-            match to_hashset(&_content) {
-                Ok(set) => {
-
-                    let ori_set: HashSet<String> = FINAL.iter().map(|s| s.to_string()).collect();
-                    
-                    if !set.is_subset(&ori_set) {
-                        return Err(Error::BeyondBuiltinWords)
-                    }
-                    else {
-
-                        //renew the FINAL and sorted
-                        _content.sort_by(|a, b| a.cmp(&b));
-                        self.FINAL = _content;
-                        return Ok(())
-                    }
-                
-                }
+    //An empty bank for a length not covered by the builtin words
+    pub fn with_length(length: usize) -> Words {
+        if length == BUILTIN_LENGTH {
+            return Words::new();
+        }
+        Words{FINAL: Vec::new(), ACCEPTABLE: Vec::new(), length, external: false}
+    }
 
-                Err(Error::DuplicatedWords) => return Err(Error::DuplicatedWords),
+    pub fn FINAL_set(&mut self, _final_set: String) -> Result<(),Error>{
+        //read from file provided, renew the FINAL and sorted
+        self.FINAL = read_set(&_final_set, self.length, self.builtin(FINAL))?;
+        Ok(())
+    }
 
-                _ => unimplemented!()
-            }
+    //Same logic with FINAL_set
+    pub fn ACCEPTABLE_set(&mut self, _acceptable_set: String) -> Result<(),Error>{
+        self.ACCEPTABLE = read_set(&_acceptable_set, self.length, self.builtin(ACCEPTABLE))?;
+        Ok(())
+    }
 
-            Ok(())
+    //The builtin list a word-set has to stay in, if any
+    fn builtin(&self, list: &'static [&'static str]) -> Option<&'static [&'static str]> {
+        if self.length == BUILTIN_LENGTH && !self.external {
+            Some(list)
         }
         else {
-            Err(Error::FileNotExist)
+            None
         }
     }
 
-    //Same logic with FINAL_set
-    pub fn ACCEPTABLE_set(&mut self, _acceptable_set: String) -> Result<(),Error>{
-        if let Ok(mut f) = std::fs::File::open(_acceptable_set) {
 
-            let mut _content = String::new();
-            std::io::Read::read_to_string(&mut f, &mut _content).unwrap();
-
-            let mut _content: Vec<String> = _content.lines().map(|s| s.to_string().to_lowercase()).collect();
-
-            match to_hashset(&_content) {
-                Ok(set) => {
-
-                    let ori_set: HashSet<String> = ACCEPTABLE.iter().map(|s| s.to_string()).collect();
-                    
-                    if !set.is_subset(&ori_set) {
-                        return Err(Error::BeyondBuiltinWords)
-                    }
-                    else {
-                        _content.sort_by(|a, b| a.cmp(&b));
-                        self.ACCEPTABLE = _content;
-                        return Ok(())
-                    }
-                
-                }
+    //Build a bank of the given word length from the optional final-set and acceptable-set files.
+    //With allow_external the files may hold words the builtin lists do not know.
+    pub fn from_files(length: usize, _final_set: Option<String>, _acceptable_set: Option<String>, allow_external: bool) -> Result<Words, Error> {
+        if !(MIN_LENGTH..=MAX_LENGTH).contains(&length) {
+            return Err(Error::LengthOutOfRange(length));
+        }
+        if length != BUILTIN_LENGTH && (_final_set.is_none() || _acceptable_set.is_none()) {
+            return Err(Error::NoBuiltinWords);
+        }
 
-                Err(Error::DuplicatedWords) => return Err(Error::DuplicatedWords),
+        let mut _words = Words::with_length(length);
+        _words.external = allow_external;
+        if let Some(f) = &_final_set {
+            _words.FINAL_set(f.clone())?;
+        }
+        if let Some(a) = &_acceptable_set {
+            _words.ACCEPTABLE_set(a.clone())?;
+        }
 
-                _ => unimplemented!()
+        //Every answer has to be a word the player is allowed to guess,
+        //checked against the builtin ACCEPTABLE too when no acceptable-set is given
+        if let Some(f) = _final_set {
+            let acceptable: HashSet<&str> = _words.ACCEPTABLE.iter().map(|w| w.as_str()).collect();
+            let unreachable: Vec<String> = _words.FINAL.iter()
+                .filter(|w| !acceptable.contains(w.as_str()))
+                .cloned()
+                .collect();
+            if !unreachable.is_empty() {
+                return Err(Error::UnreachableAnswers { path: f, words: unreachable });
             }
-
-            Ok(())
         }
-        else {
-            Err(Error::FileNotExist)
+        if _words.FINAL.is_empty() {
+            return Err(Error::EmptyFinalSet);
+        }
+        Ok(_words)
+    }
+
+    //The answer of a given day (counting from 1) after shuffling FINAL with the seed.
+    //There are as many days as words in FINAL.
+    pub fn pick_answer(&mut self, seed: u64, day: u64) -> Result<String, Error> {
+        if day == 0 || day > self.FINAL.len() as u64 {
+            return Err(Error::DayOutOfRange { day, days: self.FINAL.len() });
         }
+        self.FINAL_RAND(seed);
+        Ok(self.FINAL[(day - 1) as usize].clone())
     }
 
+    //The answers of the boards of a multi-board game on a given day.
+    //Each day takes the next run of words from the shuffled FINAL, so boards never share an answer
+    //and there are as many days as whole runs in FINAL.
+    pub fn pick_answers(&mut self, seed: u64, day: u64, boards: usize) -> Result<Vec<String>, Error> {
+        if self.FINAL.len() < boards {
+            return Err(Error::TooFewAnswers { boards, answers: self.FINAL.len() });
+        }
+        let days = self.FINAL.len() / boards;
+        if day == 0 || day > days as u64 {
+            return Err(Error::DayOutOfRange { day, days });
+        }
+        self.FINAL_RAND(seed);
+        let start = (day - 1) as usize * boards;
+        Ok(self.FINAL[start..start + boards].to_vec())
+    }
 
     //A rand generator, updating the FINAL at the same time
     pub fn FINAL_RAND(&mut self, seed: u64) {
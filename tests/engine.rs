@@ -0,0 +1,423 @@
+use wordle::game::{Error, Game, GameState, LetterState};
+use wordle::words_gen::Words;
+
+fn states(word: &[LetterState]) -> String {
+    word.iter().map(|s| s.to_state()).collect()
+}
+
+#[test]
+fn test_engine_win() {
+    let mut game = Game::new("cargo", Words::new(), false, 6).ok().unwrap();
+
+    let result = game.guess("hello").ok().unwrap();
+    assert_eq!(states(&result.states), "RRRRG");
+    assert!(result.game_state == GameState::Going);
+    assert_eq!(result.letters[&'o'].to_state(), 'G');
+
+    let result = game.guess("CARGO").ok().unwrap();
+    assert_eq!(states(&result.states), "GGGGG");
+    assert!(result.game_state == GameState::Win);
+    assert_eq!(game.guesses, vec!["HELLO", "CARGO"]);
+
+    assert!(matches!(game.guess("hello"), Err(Error::GameOver)));
+}
+
+#[test]
+fn test_engine_rejects_and_loses() {
+    assert!(matches!(Game::new("zzzzz", Words::new(), false, 6), Err(Error::NotInWordList)));
+
+    let mut game = Game::new("cargo", Words::new(), true, 6).ok().unwrap();
+    assert!(matches!(game.guess("abcde"), Err(Error::NotInWordList)));
+
+    game.guess("hello").ok().unwrap();
+    assert!(matches!(game.guess("world"), Err(Error::DisobeyingDifficult(_))));
+    assert!(game.guesses.len() == 1);
+
+    for _ in 0..5 {
+        game.guess("hello").ok().unwrap();
+    }
+    assert!(game.game_state == GameState::Lose);
+}
+
+#[test]
+fn test_engine_word_length() {
+    let mut words = Words::with_length(6);
+    words.FINAL = vec!["planet".to_string()];
+    words.ACCEPTABLE = vec!["planet".to_string(), "street".to_string()];
+
+    let mut game = Game::new("planet", words, false, 6).ok().unwrap();
+    assert_eq!(game.length(), 6);
+    assert!(matches!(game.guess("plane"), Err(Error::NotInWordList)));
+
+    let result = game.guess("street").ok().unwrap();
+    assert_eq!(states(&result.states), "RRRRGG");
+}
+
+#[test]
+fn test_engine_max_guesses() {
+    let mut game = Game::new("cargo", Words::new(), false, 4).ok().unwrap();
+    for _ in 0..3 {
+        game.guess("hello").ok().unwrap();
+    }
+    assert!(game.game_state == GameState::Going);
+    assert!(game.guess("hello").ok().unwrap().game_state == GameState::Lose);
+}
+
+#[test]
+fn test_word_set_errors() {
+    let path = std::env::temp_dir().join("wordle_test_duplicated.txt");
+    std::fs::write(&path, "cargo\nhello\ncargo\n").unwrap();
+    let path = path.to_str().unwrap().to_string();
+
+    match Words::from_files(5, Some(path.clone()), None, false) {
+        Err(wordle::error::Error::DuplicatedWords { path: p, line, word }) => {
+            assert_eq!((p, line, word.as_str()), (path, 3, "cargo"));
+        }
+        _ => panic!("duplicated word not reported"),
+    }
+
+    let missing = Words::from_files(5, None, Some("no_such_file.txt".to_string()), false).err().unwrap();
+    assert_eq!(missing.to_string(), "no_such_file.txt: file does not exist");
+    assert_eq!(missing.exit_code(), 3);
+
+    let binary = std::env::temp_dir().join("wordle_test_binary.txt");
+    std::fs::write(&binary, [0xff, 0xfe, b'\n']).unwrap();
+    match Words::from_files(5, Some(binary.to_str().unwrap().to_string()), None, false) {
+        Err(e @ wordle::error::Error::FileUnreadable { .. }) => assert_eq!(e.exit_code(), 20),
+        _ => panic!("a file not in UTF-8 reported as something else"),
+    }
+
+    let short = std::env::temp_dir().join("wordle_test_short.txt");
+    std::fs::write(&short, "cargo\nhi\n").unwrap();
+    let wrong_length = Words::from_files(5, Some(short.to_str().unwrap().to_string()), None, false).err().unwrap();
+    assert!(matches!(wrong_length, wordle::error::Error::WrongLength { line: 2, .. }));
+    assert_ne!(wrong_length.exit_code(), Words::from_files(9, None, None, false).err().unwrap().exit_code());
+
+    let empty = std::env::temp_dir().join("wordle_test_empty.txt");
+    std::fs::write(&empty, "").unwrap();
+    assert!(matches!(Words::from_files(5, Some(empty.to_str().unwrap().to_string()), None, false),
+        Err(wordle::error::Error::EmptyFinalSet)));
+
+    //a random game has as many days as answers
+    std::fs::write(&short, "cargo\nhello\n").unwrap();
+    let mut words = Words::from_files(5, Some(short.to_str().unwrap().to_string()), None, false).ok().unwrap();
+    assert!(words.pick_answer(1, 2).is_ok());
+    for day in [0, 3] {
+        assert!(matches!(words.pick_answer(1, day), Err(wordle::error::Error::DayOutOfRange { days: 2, .. })));
+    }
+}
+
+#[test]
+fn test_unreachable_answers() {
+    let dir = std::env::temp_dir();
+    let (f, a) = (dir.join("wordle_test_unreachable_final.txt"), dir.join("wordle_test_unreachable_acceptable.txt"));
+    std::fs::write(&f, "world\ncargo\nhello\n").unwrap();
+    std::fs::write(&a, "hello\nworld\n").unwrap();
+
+    match Words::from_files(5, Some(f.to_str().unwrap().to_string()), Some(a.to_str().unwrap().to_string()), false) {
+        Err(wordle::error::Error::UnreachableAnswers { words, .. }) => assert_eq!(words, vec!["cargo".to_string()]),
+        _ => panic!("unreachable answer not reported"),
+    }
+}
+
+#[test]
+fn test_external_words() {
+    let dir = std::env::temp_dir();
+    let (f, a) = (dir.join("wordle_test_external_final.txt"), dir.join("wordle_test_external_acceptable.txt"));
+    std::fs::write(&f, "zorbo\n").unwrap();
+    std::fs::write(&a, "zorbo\nhello\n").unwrap();
+    let (f, a) = (f.to_str().unwrap().to_string(), a.to_str().unwrap().to_string());
+
+    match Words::from_files(5, Some(f.clone()), Some(a.clone()), false) {
+        Err(wordle::error::Error::BeyondBuiltinWords { line: 1, .. }) => {}
+        _ => panic!("external word let in without the flag"),
+    }
+
+    let words = Words::from_files(5, Some(f.clone()), Some(a.clone()), true).ok().unwrap();
+    let mut game = Game::new("zorbo", words, false, 6).ok().unwrap();
+    assert!(game.guess("zorbo").ok().unwrap().game_state == GameState::Win);
+
+    //without an acceptable-set the answers still have to be in the builtin one
+    match Words::from_files(5, Some(f.clone()), None, true) {
+        Err(wordle::error::Error::UnreachableAnswers { words, .. }) => assert_eq!(words, vec!["zorbo".to_string()]),
+        _ => panic!("answer beyond the builtin acceptable words let in"),
+    }
+
+    std::fs::write(&a, "zorbo\nhe-lo\n").unwrap();
+    match Words::from_files(5, Some(f), Some(a), true) {
+        Err(wordle::error::Error::WrongAlphabet { line: 2, word, .. }) => assert_eq!(word, "he-lo"),
+        _ => panic!("word out of the alphabet let in"),
+    }
+}
+
+#[test]
+fn test_multi_boards() {
+    use wordle::multi::MultiGame;
+
+    let answers = vec!["cargo".to_string(), "otter".to_string()];
+    let mut game = MultiGame::new(&answers, Words::new(), false, 7).ok().unwrap();
+
+    assert!(matches!(game.guess("abcde"), Err(Error::NotInWordList)));
+
+    let results = game.guess("cargo").ok().unwrap();
+    assert_eq!(states(&results[0].as_ref().unwrap().states), "GGGGG");
+    assert_eq!(states(&results[1].as_ref().unwrap().states), "RRYRY");
+    assert!(game.game_state == GameState::Going);
+
+    //the solved board takes no more guesses
+    let results = game.guess("otter").ok().unwrap();
+    assert!(results[0].is_none());
+    assert!(game.game_state == GameState::Win);
+    assert_eq!(game.boards[0].guesses.len(), 1);
+
+    let record = game.to_state();
+    assert_eq!(record.answers, vec!["CARGO", "OTTER"]);
+    assert!(record.is_win());
+    assert_eq!(record.allowed_guesses(), 7);
+    assert_eq!(wordle::state::Game { max_guesses: None, ..record.clone() }.allowed_guesses(), 7);
+
+    //random boards never share an answer, the days end when FINAL runs out
+    let mut words = Words::new();
+    let days = words.FINAL.len() / 4;
+    let picked = words.pick_answers(2024, days as u64, 4).ok().unwrap();
+    assert!(picked.iter().all(|a| picked.iter().filter(|b| *b == a).count() == 1));
+    assert!(matches!(words.pick_answers(2024, days as u64 + 1, 4), Err(wordle::error::Error::DayOutOfRange { .. })));
+    assert!(matches!(words.pick_answers(2024, 0, 2), Err(wordle::error::Error::DayOutOfRange { .. })));
+    words.FINAL.truncate(2);
+    assert!(matches!(words.pick_answers(2024, 1, 4), Err(wordle::error::Error::TooFewAnswers { boards: 4, answers: 2 })));
+}
+
+#[test]
+fn test_adversarial() {
+    let mut game = Game::adversarial(Words::new(), false, 6).ok().unwrap();
+
+    let result = game.guess("salet").ok().unwrap();
+    assert_eq!(states(&result.states), "RRRRR");
+    assert_eq!(game.pool.len(), 221);
+    assert!(!game.answer.chars().any(|c| "salet".contains(c)));
+
+    //an answer still in the pool is dodged while others are left
+    let dodged = game.pool[0].clone();
+    let result = game.guess(&dodged).ok().unwrap();
+    assert_ne!(states(&result.states), "GGGGG");
+    assert!(game.game_state == GameState::Going);
+    assert!(game.pool.iter().all(|w| w != &dodged));
+}
+
+#[test]
+fn test_daily_day() {
+    use chrono::NaiveDate;
+    use wordle::daily;
+
+    let epoch = daily::default_epoch();
+    assert_eq!(daily::day_of(epoch, epoch, 100), 1);
+    assert_eq!(daily::day_of(NaiveDate::from_ymd_opt(2024, 7, 31).unwrap(), epoch, 100), 31);
+    //after the last answer the days start over
+    assert_eq!(daily::day_of(NaiveDate::from_ymd_opt(2024, 10, 9).unwrap(), epoch, 100), 1);
+    assert_eq!(daily::day_of(NaiveDate::from_ymd_opt(2024, 6, 30).unwrap(), epoch, 100), 100);
+}
+
+#[test]
+fn test_share_text() {
+    let mut game = Game::new("cargo", Words::new(), true, 6).ok().unwrap();
+    game.guess("hello").ok().unwrap();
+    game.guess("cargo").ok().unwrap();
+
+    assert_eq!(wordle::share::share_text(&game, Some(123), false), "Wordle 123 2/6*\n⬛⬛⬛⬛🟩\n🟩🟩🟩🟩🟩");
+    assert_eq!(wordle::share::share_text(&game, None, true), "Wordle 2/6*\n⬛⬛⬛⬛🟧\n🟧🟧🟧🟧🟧");
+
+    let answers = vec!["cargo".to_string(), "otter".to_string()];
+    let mut multi = wordle::multi::MultiGame::new(&answers, Words::new(), false, 7).ok().unwrap();
+    multi.guess("cargo").ok().unwrap();
+    multi.guess("otter").ok().unwrap();
+    assert_eq!(wordle::share::share_multi(&multi, Some(4), false),
+        "Wordle 4 2/7\n🟩🟩🟩🟩🟩 ⬛⬛🟨⬛🟨\n           🟩🟩🟩🟩🟩");
+}
+
+#[test]
+fn test_state_versions() {
+    use wordle::state;
+
+    //a version 1 file is read with the win of each game filled in, and written back as it was
+    let mut legacy = state::load_state("tests/cases/07_02_load_state.before.json").ok().unwrap();
+    assert_eq!(legacy.version, 1);
+    assert!(legacy.games[0].is_win());
+    assert!(!legacy.to_json().contains("version"));
+    assert!(!legacy.to_json().contains("win"));
+
+    //migrated, it keeps the games and writes what the new version has room for
+    legacy.version = state::VERSION;
+    let json = legacy.to_json();
+    assert!(json.contains("\"version\": 2"));
+    assert!(json.contains("\"win\": true"));
+
+    let fresh = state::load_state("tests/data/no_such_state.json").ok().unwrap();
+    assert_eq!(fresh.version, state::VERSION);
+
+    //a record tells the word-sets to replay it with
+    let dir = std::env::temp_dir();
+    let (f, a) = (dir.join("wordle_test_replay_final.txt"), dir.join("wordle_test_replay_acceptable.txt"));
+    std::fs::write(&f, "planet\n").unwrap();
+    std::fs::write(&a, "planet\nrocket\n").unwrap();
+    let sets = state::WordSets {
+        final_set: Some(f.to_str().unwrap().to_string()),
+        acceptable_set: Some(a.to_str().unwrap().to_string()),
+        length: 6,
+        external: false,
+    };
+    assert!(!serde_json::to_string(&sets).unwrap().contains("external"));
+    let mut game = Game::new("planet", sets.to_words().ok().unwrap(), false, 6).ok().unwrap();
+    assert!(game.guess("rocket").is_ok());
+}
+
+#[test]
+fn test_state_recovery() {
+    use wordle::state;
+
+    let path = std::env::temp_dir().join(format!("wordle_recovery_{}.json", std::process::id()));
+    let path = path.to_str().unwrap();
+
+    //every save keeps what was there as the backup
+    state::update(path, |s| s.total_rounds = 1).ok().unwrap();
+    state::update(path, |s| s.total_rounds = 2).ok().unwrap();
+    std::fs::write(path, "{\"total_rounds\": 2, \"gam").unwrap();
+
+    let recovered = state::load_state(path).ok().unwrap();
+    assert!(recovered.recovered);
+    assert_eq!(recovered.total_rounds, 1);
+
+    //the damaged file is not backed up over the good one
+    state::update(path, |s| s.total_rounds += 1).ok().unwrap();
+    assert_eq!(state::load_state(&format!("{}.bak", path)).ok().unwrap().total_rounds, 1);
+
+    //a file not in UTF-8 is damaged too, not a fresh start
+    std::fs::write(path, [0xff, 0xfe, b'{', b'"']).unwrap();
+    let recovered = state::load_state(path).ok().unwrap();
+    assert!(recovered.recovered);
+    assert_eq!(recovered.total_rounds, 1);
+    state::update(path, |s| s.total_rounds += 1).ok().unwrap();
+    assert_eq!(state::load_state(path).ok().unwrap().total_rounds, 2);
+    assert_eq!(state::load_state(&format!("{}.bak", path)).ok().unwrap().total_rounds, 1);
+
+    //with no good backup the damage is an error
+    std::fs::write(format!("{}.bak", path), "[").unwrap();
+    std::fs::write(path, "[").unwrap();
+    assert!(matches!(state::load_state(path), Err(wordle::error::Error::StateDamaged { .. })));
+
+    for ext in ["", ".bak", ".lock"] {
+        let _ = std::fs::remove_file(format!("{}{}", path, ext));
+    }
+}
+
+#[test]
+fn test_stats() {
+    use wordle::{state, stats};
+
+    let game = |answer: &str, guesses: &[&str]| state::Game {
+        answer: answer.to_string(),
+        guesses: guesses.iter().map(|g| g.to_string()).collect(),
+        ..state::Game_new()
+    };
+    let games = vec![
+        game("CARGO", &["CRANE", "CARGO"]),
+        game("HELLO", &["CRANE", "WORLD", "HELLO"]),
+        game("SPURN", &["SALET", "CRANE", "BLIMP", "FJORD", "WHACK", "GUSTY"]),
+        game("CARGO", &["SALET", "CARGO"]),
+    ];
+    let report = stats::compute(&games);
+
+    assert_eq!(report.games, 4);
+    assert_eq!(report.wins, 3);
+    assert_eq!(report.win_percentage, 75.0);
+    assert_eq!((report.current_streak, report.max_streak), (1, 2));
+    assert_eq!(report.histogram, vec![0, 2, 1, 0, 0, 0]);
+    //a loss counts as seven guesses
+    assert_eq!(report.starts[0].word, "CRANE");
+    assert_eq!(report.starts[0].average, 2.5);
+    assert_eq!(report.starts[1].average, 4.5);
+    assert_eq!(report.answers[0].word, "SPURN");
+    assert_eq!(report.answers[0].average, 7.0);
+    assert_eq!(report.letters[0].letter, 'A');
+    assert!(report.to_csv().contains("histogram,X,1\n"));
+}
+
+#[test]
+fn test_streak() {
+    use wordle::state::{self, Streak};
+
+    let mut streak = Streak::default();
+    for win in [true, true, false, true, true, true, false, true] {
+        streak.record(win);
+    }
+    assert_eq!(streak, Streak { current: 1, best: 3 });
+
+    //a file written without the streak has it worked out from its games
+    let legacy = state::load_state("tests/cases/07_02_load_state.before.json").ok().unwrap();
+    assert_eq!(legacy.streak, Streak { current: 6, best: 6 });
+    assert!(!legacy.to_json().contains("streak"));
+}
+
+#[test]
+fn test_players() {
+    use wordle::{state, stats};
+
+    let game = |answer: &str, guesses: &[&str]| state::Game {
+        answer: answer.to_string(),
+        guesses: guesses.iter().map(|g| g.to_string()).collect(),
+        ..state::Game_new()
+    };
+
+    //a player saving into a version 1 file upgrades it, the others are left at the top
+    let mut file = state::load_state("tests/cases/07_02_load_state.before.json").ok().unwrap();
+    file.record(Some("ann"), game("CARGO", &["CARGO"]));
+    file.record(Some("bob"), game("CARGO", &["HELLO", "WORLD", "CRANE", "AUDIO", "BLIMP", "FJORD"]));
+    file.record(None, game("CARGO", &["CRANE", "CARGO"]));
+    assert_eq!(file.version, state::VERSION);
+    assert_eq!(file.games_of(None).len(), 7);
+    assert_eq!(file.games_of(Some("ann")).len(), 1);
+    assert_eq!(file.streak_of(Some("bob")), state::Streak { current: 0, best: 0 });
+    assert!(file.games_of(Some("eve")).is_empty());
+
+    let board = stats::leaderboard(&file.all_players());
+    let names: Vec<&str> = board.iter().map(|s| s.player.as_str()).collect();
+    assert_eq!(names, vec!["ann", state::DEFAULT_PLAYER, "bob"]);
+}
+
+#[test]
+fn test_difficult_violation() {
+    use wordle::game::Violation;
+
+    let mut game = Game::new("cargo", Words::new(), true, 6).ok().unwrap();
+    game.guess("crane").ok().unwrap();
+
+    //C is green, R and A are yellow
+    match game.guess("trace") {
+        Err(Error::DisobeyingDifficult(v)) => {
+            assert_eq!(v, Violation::Green { position: 0, letter: 'c' });
+            assert_eq!(v.to_string(), "1st letter must be C");
+        }
+        _ => panic!("trace moves C from its place"),
+    }
+    match game.guess("cruel") {
+        Err(Error::DisobeyingDifficult(v)) => assert_eq!(v.to_string(), "Guess must contain A"),
+        _ => panic!("cruel keeps C and R but leaves A out"),
+    }
+    assert_eq!(Violation::Green { position: 1, letter: 'r' }.to_string(), "2nd letter must be R");
+}
+
+#[test]
+fn test_multi_violation() {
+    use wordle::game::Violation;
+    use wordle::multi::MultiGame;
+
+    let answers = vec!["cargo".to_string(), "hello".to_string()];
+    let mut game = MultiGame::new(&answers, Words::new(), true, 7).ok().unwrap();
+    game.guess("lemon").ok().unwrap();
+
+    //the first board whose hint is broken tells why the guess is turned down
+    match game.guess("crane") {
+        Err(Error::DisobeyingDifficult(v)) => assert_eq!(v, Violation::Yellow { letter: 'o' }),
+        _ => panic!("crane leaves O out"),
+    }
+    assert_eq!(game.guesses.len(), 1);
+}
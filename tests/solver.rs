@@ -0,0 +1,48 @@
+use wordle::game::{score, Game, GameState, LetterState};
+use wordle::solver::{candidates, Solver, Strategy};
+use wordle::words_gen::Words;
+
+fn states(word: &[LetterState]) -> String {
+    word.iter().map(|s| s.to_state()).collect()
+}
+
+#[test]
+fn test_score_repeated_letters() {
+    assert_eq!(states(&score("hello", "world")), "RRRGY");
+    assert_eq!(states(&score("lolly", "hello")), "RYGGR");
+    assert_eq!(states(&score("eerie", "ether")), "GYYRR");
+}
+
+#[test]
+fn test_solver_plays_to_the_end() {
+    for strategy in [Strategy::Entropy, Strategy::Worst] {
+        let mut game = Game::new("cargo", Words::new(), true, 6).ok().unwrap();
+        let mut solver = Solver::new(game.word_bank(), strategy);
+        let mut guess = "salet".to_string();
+
+        while game.game_state == GameState::Going {
+            let result = game.guess(&guess).ok().unwrap();
+            solver.update(&guess, &result.states);
+            assert!(solver.candidates.contains(&"cargo".to_string()));
+            assert_eq!(solver.candidates, candidates(&game));
+
+            if let Some(next) = solver.best_guess(&game) {
+                guess = next;
+            }
+        }
+        assert!(game.game_state == GameState::Win);
+    }
+}
+
+#[test]
+fn test_bench_report() {
+    let words = Words::new();
+    let answers = vec!["otter".to_string(), "debar".to_string(), "bribe".to_string()];
+    let report = wordle::bench::run(&words, &answers, Strategy::Entropy, Some("salet".to_string()), false, 6, 2);
+
+    assert_eq!(report.games.len(), 3);
+    assert_eq!(report.distribution.iter().sum::<usize>() + report.failures.len(), 3);
+    assert!(report.games.iter().all(|g| g.guesses[0] == "SALET"));
+    assert_eq!(report.worst[0].answer, "BRIBE");
+    assert!(report.to_csv().starts_with("answer,guesses,win,path\nOTTER,3,true,"));
+}